@@ -12,12 +12,15 @@ import {
 } from "@solana/kit";
 import { loadSolanaContext } from "../../helpers.js";
 import {
+  deriveRewardAccountsPda,
+  getInitConfigInstructionAsync,
   getInitInstructionAsync,
   getInitIntermediaryVaultInstructionAsync,
 } from "@effectai/reward";
 
 interface RewardInitOptions {
   mint: string;
+  treasury: string;
 }
 
 export const registerInitRewardCommand = (program: Command) =>
@@ -28,6 +31,10 @@ export const registerInitRewardCommand = (program: Command) =>
       "--mint <mint>",
       "The mint address of the token that belongs to the reward program",
     )
+    .requiredOption(
+      "--treasury <treasury>",
+      "The token account receiving the protocol fee of the reward pool",
+    )
     .action(async (options: RewardInitOptions) => {
       const mint = address(options.mint);
       const { rpc, rpcSubscriptions, signer } = await loadSolanaContext();
@@ -49,6 +56,17 @@ export const registerInitRewardCommand = (program: Command) =>
         },
       );
 
+      const { reflectionAccount } = await deriveRewardAccountsPda({ mint });
+
+      const initConfigIx = await getInitConfigInstructionAsync({
+        authority: signer,
+        reflectionAccount,
+        treasuryTokenAccount: address(options.treasury),
+        feeBasisPoints: 0,
+        feeCap: 0,
+        paused: false,
+      });
+
       const recentBlockhash = await rpc.getLatestBlockhash().send();
 
       const transactionMessage = pipe(
@@ -61,7 +79,7 @@ export const registerInitRewardCommand = (program: Command) =>
           ),
         (tx) =>
           appendTransactionMessageInstructions(
-            [initIx, initIntermediaryIx],
+            [initIx, initIntermediaryIx, initConfigIx],
             tx,
           ),
       );
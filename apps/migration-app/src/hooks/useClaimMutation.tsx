@@ -73,9 +73,10 @@ export const useClaimMutation = () => {
           mint,
           amount: 0,
           userTokenAccount,
-          duration: 30 * 24 * 60 * 60,
+          tier: 0, // 30 days
           stakeAccount: newStakeAccount,
           authority: signer,
+          migrationAccount,
         });
 
         instructions.push(stakeIx);
@@ -134,7 +134,7 @@ export const useStakeMutation = () => {
         mint,
         stakeAccount,
         amount: amount * 1e6,
-        duration: 30 * 24 * 60 * 60, // 30 days
+        tier: 0, // 30 days
         authority: signer,
         userTokenAccount,
       });
@@ -96,13 +96,23 @@ export const calculateDue = (
   vestingAccount: Account<VestingAccount>,
   amountAvailable: number,
 ): bigint => {
-  const { startTime, releaseRate, distributedTokens } = vestingAccount.data;
+  const { startTime, releaseRate, distributedTokens, duration } =
+    vestingAccount.data;
   const now = Math.floor(new Date().getTime() / 1000);
 
   if (now < startTime) {
     return 0n;
   }
 
+  // scheduled vestings release everything linearly over their duration
+  if (duration > 0) {
+    const elapsed = BigInt(Math.min(now - Number(startTime), duration));
+    const totalAmount = distributedTokens + BigInt(amountAvailable);
+    const vestedAmount = (totalAmount * elapsed) / BigInt(duration);
+
+    return vestedAmount - distributedTokens;
+  }
+
   const poolAmount = (BigInt(now) - startTime) * releaseRate;
   const amountDue = Number(poolAmount - distributedTokens);
 
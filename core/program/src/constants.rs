@@ -8,6 +8,11 @@ pub const STAKE_DURATION_MIN: u128 = 30 * SECONDS_PER_DAY; // 30 days
 pub const STAKE_DURATION_MAX: u128 = 365 * SECONDS_PER_DAY; // 1 year
 pub const STAKE_MINIMUM_AMOUNT: u64 = 0;
 pub const STAKE_AGE_MAX_DAYS: u64 = 1000; 
+pub const BASIS_POINTS: u64 = 10_000; // 100%
+pub const LOCK_TIER_MULTIPLIER_MAX: u64 = 15_000; // the largest weight multiplier of a stake
+pub const SLASH_MAX_BASIS_POINTS: u64 = 1_000; // 10%
+pub const SYNC_MIN_COMPUTE_UNITS: u64 = 15_000; // reserved per synced reward account
+pub const EPOCH_CLAIM_DAYS: u64 = 90; // after which the unclaimed part of an epoch is swept
 
 #[cfg(not(feature = "mainnet"))]
 pub const CLAIM_START_TIME: i64 = 1704452400; // 2024-01-05 12:00:00 UTC
@@ -18,3 +23,13 @@ pub const CLAIM_START_TIME: i64 = 1736074800; // 2025-01-05 12:00:00 UTC
 pub const UNSTAKE_DELAY_DAYS: u64 = 0;
 #[cfg(feature = "mainnet")]
 pub const UNSTAKE_DELAY_DAYS: u64 = 7;
+
+#[cfg(not(feature = "mainnet"))]
+pub const DELEGATION_COOLDOWN_DAYS: u64 = 0;
+#[cfg(feature = "mainnet")]
+pub const DELEGATION_COOLDOWN_DAYS: u64 = 7;
+
+#[cfg(not(feature = "mainnet"))]
+pub const SLASH_COOLDOWN_DAYS: u64 = 0;
+#[cfg(feature = "mainnet")]
+pub const SLASH_COOLDOWN_DAYS: u64 = 30;
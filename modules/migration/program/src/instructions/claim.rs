@@ -68,9 +68,57 @@ pub struct ClaimStake<'info> {
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub staking_program: Program<'info, EffectStaking>,
+
+    /// CHECK: the event authority of the staking program, used for its event CPIs.
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = staking_program.key(),
+    )]
+    pub staking_event_authority: UncheckedAccount<'info>,
+
+    /// CHECK: the total checkpoints of the staking program, validated by the staking program.
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+        seeds::program = staking_program.key(),
+    )]
+    pub total_checkpoint_account: UncheckedAccount<'info>,
+
+    /// CHECK: the stake checkpoints, validated by the staking program.
+    #[account(mut)]
+    pub stake_checkpoint_account: UncheckedAccount<'info>,
+
+    /// CHECK: the reward program, validated by the staking program.
+    pub reward_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the reward account of the stake, validated by the staking program.
+    #[account(mut)]
+    pub reward_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the reflection of the reward pool, validated by the staking program.
+    #[account(mut)]
+    pub reflection_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the emission of the reward pool, validated by the reward program.
+    #[account(mut)]
+    pub emission_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the delegation of the stake, validated by the staking program.
+    #[account(mut)]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: the operator of the delegation, validated by the staking program.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
 }
 
-pub fn handler(ctx: Context<ClaimStake>, signature: Vec<u8>, message: Vec<u8>) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, ClaimStake<'info>>,
+    signature: Vec<u8>,
+    message: Vec<u8>,
+) -> Result<()> {
     let now = Clock::get()?.unix_timestamp;
 
     if now < CLAIM_START_TIME {
@@ -89,8 +137,12 @@ pub fn handler(ctx: Context<ClaimStake>, signature: Vec<u8>, message: Vec<u8>) -
         ctx.accounts.migration_account.foreign_address.clone(),
     )?;
 
-    // topup the stake account
-    genesis_stake!(ctx.accounts, &[&vault_seed!(ctx.accounts.migration_account.key(), *ctx.program_id)[..]])?;
+    // topup the stake account, the reward pools of the stake are given as remaining accounts
+    genesis_stake!(
+        ctx.accounts,
+        &[&vault_seed!(ctx.accounts.migration_account.key(), *ctx.program_id)[..]],
+        ctx.remaining_accounts
+    )?;
 
     close_migration_vault!(
 	ctx.accounts,
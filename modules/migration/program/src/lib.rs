@@ -19,8 +19,8 @@ pub mod effect_migration {
 
     use super::*;
 
-    pub fn claim_stake(
-        ctx: Context<ClaimStake>,
+    pub fn claim_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimStake<'info>>,
         signature: Vec<u8>,
         message: Vec<u8>,
     ) -> Result<()> {
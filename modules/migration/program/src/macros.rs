@@ -8,7 +8,7 @@ macro_rules! vault_seed {
 
 #[macro_export]
 macro_rules! genesis_stake {
-    ($accounts: expr, $seeds:expr) => {
+    ($accounts: expr, $seeds:expr, $pools:expr) => {
         crate::effect_staking::cpi::stake_genesis(
             CpiContext::new_with_signer(
                 $accounts.staking_program.to_account_info(),
@@ -28,9 +28,35 @@ macro_rules! genesis_stake {
                     system_program: $accounts.system_program.to_account_info(),
                     token_program: $accounts.token_program.to_account_info(),
                     rent: $accounts.rent.to_account_info(),
+                    event_authority: $accounts.staking_event_authority.to_account_info(),
+                    program: $accounts.staking_program.to_account_info(),
+                    total_checkpoint_account: $accounts.total_checkpoint_account.to_account_info(),
+                    stake_checkpoint_account: $accounts.stake_checkpoint_account.to_account_info(),
+                    reward_program: $accounts
+                        .reward_program
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    reward_account: $accounts
+                        .reward_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    reflection_account: $accounts
+                        .reflection_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    emission_account: $accounts
+                        .emission_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    delegation_account: $accounts.delegation_account.to_account_info(),
+                    operator_account: $accounts
+                        .operator_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
                 },
                 $seeds,
-            ),
+            )
+            .with_remaining_accounts($pools.to_vec()),
             $accounts.migration_vault_token_account.amount,
             $accounts.migration_account.stake_start_time,
         )
@@ -28,10 +28,19 @@ export const deriveRewardAccountsPda = async ({
     programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
   });
 
+  const [configAccount] = await getProgramDerivedAddress({
+    seeds: [
+      Buffer.from("config", "utf-8"),
+      getAddressEncoder().encode(reflectionAccount),
+    ],
+    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  });
+
   return {
     reflectionAccount,
     reflectionVaultAccount,
     intermediaryReflectionVaultAccount,
+    configAccount,
   };
 };
 
@@ -49,3 +58,65 @@ export const deriveStakingRewardAccountPda = async ({
     stakingRewardAccount,
   };
 };
+
+export const deriveRewardPoolAccountsPda = async ({
+  stakeMint,
+  rewardMint,
+}: {
+  stakeMint: Address;
+  rewardMint: Address;
+}) => {
+  const [reflectionAccount] = await getProgramDerivedAddress({
+    seeds: [
+      Buffer.from("reflection", "utf-8"),
+      getAddressEncoder().encode(stakeMint),
+      getAddressEncoder().encode(rewardMint),
+    ],
+    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  });
+
+  const [reflectionVaultAccount] = await getProgramDerivedAddress({
+    seeds: [getAddressEncoder().encode(reflectionAccount)],
+    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  });
+
+  const [intermediaryReflectionVaultAccount] = await getProgramDerivedAddress({
+    seeds: [getAddressEncoder().encode(reflectionVaultAccount)],
+    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  });
+
+  const [configAccount] = await getProgramDerivedAddress({
+    seeds: [
+      Buffer.from("config", "utf-8"),
+      getAddressEncoder().encode(reflectionAccount),
+    ],
+    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  });
+
+  return {
+    reflectionAccount,
+    reflectionVaultAccount,
+    intermediaryReflectionVaultAccount,
+    configAccount,
+  };
+};
+
+export const deriveStakingPoolRewardAccountPda = async ({
+  stakingAccount,
+  rewardMint,
+}: {
+  stakingAccount: Address;
+  rewardMint: Address;
+}) => {
+  const [stakingRewardAccount] = await getProgramDerivedAddress({
+    seeds: [
+      getAddressEncoder().encode(stakingAccount),
+      getAddressEncoder().encode(rewardMint),
+    ],
+    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  });
+
+  return {
+    stakingRewardAccount,
+  };
+};
@@ -17,4 +17,24 @@ pub enum RewardErrors {
     NoClaimableRewards,
     #[msg("Invalid Mint")]
     InvalidMint,
+    #[msg("Invalid emission schedule")]
+    InvalidEmission,
+    #[msg("The fee is too high.")]
+    InvalidFee,
+    #[msg("This reward pool is paused.")]
+    Paused,
+    #[msg("Invalid accounts to sync.")]
+    InvalidSyncAccounts,
+    #[msg("Invalid treasury account.")]
+    InvalidTreasury,
+    #[msg("This epoch is not open or out of the claim window.")]
+    InvalidEpoch,
+    #[msg("This epoch is already claimed.")]
+    EpochClaimed,
+    #[msg("This epoch can still be claimed.")]
+    EpochNotExpired,
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+    #[msg("The stake still takes part in reward pools.")]
+    OpenPools,
 }
\ No newline at end of file
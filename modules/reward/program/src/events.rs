@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+/***
+ * Events
+ */
+
+/// Emitted when a reward pool is topped up, with the protocol fee routed to the treasury.
+#[event]
+pub struct RewardsToppedUp {
+    pub reflection_account: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub treasury: Pubkey,
+}
+
+/// Emitted when a topup opens a new reward epoch.
+#[event]
+pub struct EpochOpened {
+    pub reflection_account: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub fee: u64,
+    pub total_weighted_amount: u64,
+}
+
+/// Emitted when a stake claims its share of a reward epoch.
+#[event]
+pub struct EpochRewardsClaimed {
+    pub reflection_account: Pubkey,
+    pub stake_account: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
+
+/// Emitted when the unclaimed part of an expired reward epoch is swept.
+#[event]
+pub struct EpochSwept {
+    pub reflection_account: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+}
@@ -13,6 +13,14 @@ pub struct Claim<'info> {
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
 
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
     #[account(
         mut,
         seeds = [reflection_account.key().as_ref()],
@@ -50,25 +58,18 @@ pub struct Claim<'info> {
 
 impl<'info> Claim<'info> {
     pub fn handler(&mut self) -> Result<()> {
+        release_emission!(self);
 
-        // determine amount to claim
-        let amount: u128 = self.reward_account.get_amount(self.reflection_account.rate);
+        // take the pending rewards and re-enter the pool with the current stake
+        let amount: u128 = self
+            .reflection_account
+            .claim(&mut self.reward_account, self.stake_account.weighted_amount)?;
 
         if amount == 0 {
             msg!("No rewards to claim");
             return Ok(());
         }
 
-        // decrease the reflection pool
-        self.reflection_account
-            .remove_rewards_account(self.reward_account.reflection, self.reward_account.weighted_amount + amount)?;
-
-        // re-enter the pool with the current stake
-        self.reward_account.update(
-            self.reflection_account.add_rewards_account(self.stake_account.weighted_amount, 0),
-            self.stake_account.weighted_amount,
-        )?;
-
         // pay-out pending reward
         transfer_tokens_from_vault!(
             self,
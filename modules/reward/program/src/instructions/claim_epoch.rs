@@ -0,0 +1,121 @@
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::*;
+use effect_common::cpi;
+use effect_staking::{
+    accounts::{StakeAccount, StakeCheckpointAccount},
+    program::EffectStaking,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct ClaimEpoch<'info> {
+    #[account(
+        seeds = [b"reflection", stake_vault_token_account.mint.as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        mut,
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    #[account(
+        mut,
+        seeds = [ b"epoch", reflection_account.key().as_ref(), &epoch.to_le_bytes() ],
+        bump,
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    #[account(
+        mut,
+        seeds = [ b"epoch_vault", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub epoch_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(has_one = authority @ RewardErrors::Unauthorized)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ b"checkpoints", stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+
+    #[account(
+        mut,
+        seeds = [ b"epochs", reward_account.key().as_ref() ],
+        bump,
+    )]
+    pub epoch_claim_account: Account<'info, EpochClaimAccount>,
+
+    #[account(
+        mut,
+        token::mint = epoch_vault_token_account.mint,
+        token::authority = authority,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub stake_program: Program<'info, EffectStaking>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimEpoch<'info> {
+    pub fn handler(&mut self, epoch: u64) -> Result<EpochRewardsClaimed> {
+        self.epoch_claim_account.claim(epoch)?;
+
+        // the stake claims for the weight it had at the snapshot of the epoch
+        let stake_checkpoint_account = self.stake_checkpoint_account.load()?;
+        let weighted_amount: u64 = checkpoint_weighted_amount_at(
+            stake_checkpoint_account.count,
+            &stake_checkpoint_account.checkpoints,
+            self.epoch_account.snapshot_time(),
+        )
+        .unwrap_or(0);
+        drop(stake_checkpoint_account);
+
+        let amount: u64 = self
+            .epoch_account
+            .claim(weighted_amount, Clock::get()?.unix_timestamp)?;
+
+        if amount > 0 {
+            self.config_account.release_epoch_amount(amount)?;
+            transfer_tokens_from_vault!(
+                self,
+                epoch_vault_token_account,
+                recipient_token_account,
+                &[epoch_vault_seed!(self.reflection_account.key().as_ref())],
+                amount
+            )?;
+        }
+
+        Ok(EpochRewardsClaimed {
+            reflection_account: self.reflection_account.key(),
+            stake_account: self.stake_account.key(),
+            epoch,
+            amount,
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use effect_common::cpi;
+use effect_staking::{accounts::StakeAccount, program::EffectStaking};
+
+#[derive(Accounts)]
+pub struct ClaimPool<'info> {
+    #[account(
+        mut,
+        seeds = [
+            b"pool",
+            stake_vault_token_account.mint.as_ref(),
+            recipient_token_account.mint.as_ref()
+        ],
+        bump,
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [pool_account.key().as_ref()],
+        bump
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        has_one = authority @ RewardErrors::Unauthorized,
+        constraint = stake_account.weighted_amount >= pool_reward_account.weighted_amount @ RewardErrors::Decreased,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref(), recipient_token_account.mint.as_ref()],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized)
+    ]
+    pub pool_reward_account: Account<'info, PoolRewardAccount>,
+
+    #[account(
+        mut,
+        token::authority = authority,
+     )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub stake_program: Program<'info, EffectStaking>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimPool<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let amount: u128 = self
+            .pool_account
+            .claim(&mut self.pool_reward_account, self.stake_account.weighted_amount)?;
+
+        if amount == 0 {
+            msg!("No rewards to claim");
+            return Ok(());
+        }
+
+        // pay-out pending reward in the reward mint
+        transfer_tokens_from_vault!(
+            self,
+            reward_vault_token_account,
+            recipient_token_account,
+            &[vault_seed!(self.pool_account.key().as_ref())],
+            amount.try_into().unwrap()
+        )
+    }
+}
@@ -1,9 +1,10 @@
 
-use anchor_spl::token::TokenAccount;
+use anchor_spl::token::{Token, TokenAccount};
 
 use effect_staking::{accounts::StakeAccount, program::EffectStaking};
 
 use crate::*;
+use effect_common::cpi;
 
 #[derive(Accounts)]
 pub struct Close<'info> {
@@ -13,12 +14,35 @@ pub struct Close<'info> {
         bump,
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reward_vault_token_account.mint,
+        token::authority = authority,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
     
     #[account(
         mut,
         close = authority,
         seeds = [ stake_account.key().as_ref() ],
         bump,       
+        constraint = reward_account.pools == 0 @ RewardErrors::OpenPools,
     )]
     pub reward_account: Account<'info, RewardAccount>,
    
@@ -40,11 +64,26 @@ pub struct Close<'info> {
     pub authority: Signer<'info>,
 
     pub stake_program: Program<'info, EffectStaking>,
+    pub token_program: Program<'info, Token>,
 }
 
 impl<'info> Close<'info> {
     pub fn handler(&mut self) -> Result<()> {
-        self.reflection_account
-            .remove_rewards_account(self.reward_account.reflection, self.reward_account.weighted_amount)
+        release_emission!(self);
+
+        // pay-out pending reward before leaving the pool
+        let amount: u128 = self.reflection_account.settle(&self.reward_account)?;
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_tokens_from_vault!(
+            self,
+            reward_vault_token_account,
+            recipient_token_account,
+            &[vault_seed!(self.reflection_account.key().as_ref())],
+            amount.try_into().unwrap()
+        )
     }
 }
@@ -0,0 +1,79 @@
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::*;
+use effect_common::cpi;
+
+#[derive(Accounts)]
+pub struct ClosePool<'info> {
+    #[account(
+        mut,
+        address = pool_reward_account.pool @ RewardErrors::InvalidSyncAccounts,
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+
+    #[account(
+        mut,
+        seeds = [ pool_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reward_vault_token_account.mint,
+        token::authority = authority,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ RewardErrors::Unauthorized,
+        has_one = stake_account @ RewardErrors::InvalidSyncAccounts,
+    )]
+    pub pool_reward_account: Account<'info, PoolRewardAccount>,
+
+    /// CHECK: the stake of the pool reward account, which may already be closed.
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// CHECK: the reward account keeping count of the pools of the stake, when still open.
+    #[account(
+        mut,
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClosePool<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        // the reward account no longer syncs this pool
+        let reward_info = self.reward_account.to_account_info();
+        if !reward_info.data_is_empty() {
+            let mut reward_account =
+                RewardAccount::try_deserialize(&mut &reward_info.try_borrow_data()?[..])?;
+            reward_account.remove_pool()?;
+            reward_account.try_serialize(&mut &mut reward_info.try_borrow_mut_data()?[..])?;
+        }
+
+        // pay-out pending reward before leaving the pool
+        let amount: u128 = self.pool_account.settle(&self.pool_reward_account)?;
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        transfer_tokens_from_vault!(
+            self,
+            reward_vault_token_account,
+            recipient_token_account,
+            &[vault_seed!(self.pool_account.key().as_ref())],
+            amount.try_into().unwrap()
+        )
+    }
+}
@@ -0,0 +1,129 @@
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+use effect_staking::{accounts::StakeAccount, program::EffectStaking};
+
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection", stake_vault_token_account.mint.as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [reflection_account.key().as_ref()],
+        bump
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = authority @ RewardErrors::Unauthorized,
+        constraint = stake_account.weighted_amount >= reward_account.weighted_amount @ RewardErrors::Decreased,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized)
+    ]
+    pub reward_account: Account<'info, RewardAccount>,
+
+    /// CHECK: validated by the staking program.
+    #[account(mut)]
+    pub total_checkpoint_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the staking program.
+    #[account(mut)]
+    pub stake_checkpoint_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the staking program.
+    #[account(mut)]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the staking program.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: the event authority of the staking program.
+    #[account(
+        seeds = [b"__event_authority"],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_event_authority: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, EffectStaking>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Compound<'info> {
+    pub fn handler(&mut self, pools: &[AccountInfo<'info>]) -> Result<()> {
+        release_emission!(self);
+
+        // take the pending rewards out of the reflection pool
+        let amount: u128 = self.reflection_account.settle(&self.reward_account)?;
+        require!(amount > 0, RewardErrors::NoClaimableRewards);
+
+        // pay the reward into the stake vault and add it to the stake
+        effect_staking::cpi::compound(
+            CpiContext::new_with_signer(
+                self.stake_program.to_account_info(),
+                effect_staking::cpi::accounts::Compound {
+                    stake_account: self.stake_account.to_account_info(),
+                    stake_vault_token_account: self.stake_vault_token_account.to_account_info(),
+                    reflection_account: self.reflection_account.to_account_info(),
+                    reward_vault_token_account: self.reward_vault_token_account.to_account_info(),
+                    total_checkpoint_account: self.total_checkpoint_account.to_account_info(),
+                    stake_checkpoint_account: self.stake_checkpoint_account.to_account_info(),
+                    delegation_account: self.delegation_account.to_account_info(),
+                    operator_account: self
+                        .operator_account
+                        .as_ref()
+                        .map(|account| account.to_account_info()),
+                    token_program: self.token_program.to_account_info(),
+                    event_authority: self.stake_event_authority.to_account_info(),
+                    program: self.stake_program.to_account_info(),
+                },
+                &[vault_seed!(self.reflection_account.key().as_ref())],
+            ),
+            amount.try_into().unwrap(),
+        )?;
+        self.stake_account.reload()?;
+
+        // re-enter the pool with the compounded stake, along with the pools of the stake
+        let weighted_amount: u128 = self.stake_account.weighted_amount;
+        self.reflection_account
+            .enter(&mut self.reward_account, weighted_amount)?;
+        sync_pools(
+            &self.stake_account.key(),
+            weighted_amount,
+            self.reward_account.pools,
+            pools,
+        )
+    }
+}
@@ -0,0 +1,92 @@
+use anchor_spl::token::{Token, TokenAccount};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ConfigureEmission<'info> {
+    #[account(mut)]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized,
+        constraint = !config_account.paused @ RewardErrors::Paused,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = EmissionAccount::SIZE,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: Account<'info, EmissionAccount>,
+
+    #[account(
+        mut,
+        seeds = [ reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [ reward_vault_token_account.key().as_ref() ],
+        bump,
+    )]
+    pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = config_account.treasury @ RewardErrors::InvalidTreasury,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ConfigureEmission<'info> {
+    pub fn handler(&mut self, rate: u64, end_time: i64) -> Result<RewardsToppedUp> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // settle what the previous schedule emitted so far
+        self.emission_account.release(&mut self.reflection_account, now)?;
+
+        // the intermediary balance is streamed instead of reflected at once
+        let balance: u64 = self.intermediate_reward_vault_token_account.amount;
+        let fee: u64 = pay_protocol_fee!(self, balance);
+        let amount: u64 = balance - fee;
+
+        if amount > 0 {
+            transfer_tokens_from_vault!(
+                self,
+                intermediate_reward_vault_token_account,
+                reward_vault_token_account,
+                &[intermediary_vault_seed!(self.reward_vault_token_account.key().as_ref())],
+                amount
+            )?;
+        }
+
+        self.emission_account.configure(
+            self.reflection_account.key(),
+            rate,
+            end_time,
+            now,
+            amount,
+        )?;
+
+        Ok(RewardsToppedUp {
+            reflection_account: self.reflection_account.key(),
+            amount,
+            fee,
+            treasury: self.treasury_token_account.key(),
+        })
+    }
+}
@@ -13,6 +13,14 @@ pub struct Enter<'info> {
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
 
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
     #[account(
         mut,
         has_one = authority @ RewardErrors::Unauthorized,
@@ -48,11 +56,10 @@ pub struct Enter<'info> {
 
 impl<'info> Enter<'info> {
     pub fn handler(&mut self) -> Result<()> {
-        self.reward_account.init(
-            self.authority.key(),
-            self.reflection_account
-                .add_rewards_account(self.stake_account.weighted_amount, 0),
-            self.stake_account.weighted_amount,
-        )
+        release_emission!(self);
+
+        self.reward_account.init(self.authority.key(), 0, 0)?;
+        self.reflection_account
+            .enter(&mut self.reward_account, self.stake_account.weighted_amount)
     }
 }
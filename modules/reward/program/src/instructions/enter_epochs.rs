@@ -0,0 +1,63 @@
+use anchor_spl::token::TokenAccount;
+
+use crate::*;
+
+use effect_staking::{accounts::StakeAccount, program::EffectStaking};
+
+#[derive(Accounts)]
+pub struct EnterEpochs<'info> {
+    #[account(
+        seeds = [b"reflection", stake_vault_token_account.mint.as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    #[account(has_one = authority @ RewardErrors::Unauthorized)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = EpochClaimAccount::SIZE,
+        seeds = [ b"epochs", reward_account.key().as_ref() ],
+        bump,
+    )]
+    pub epoch_claim_account: Account<'info, EpochClaimAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub stake_program: Program<'info, EffectStaking>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EnterEpochs<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        // the stake takes part from the next epoch on
+        self.epoch_claim_account.reward_account = self.reward_account.key();
+        self.epoch_claim_account
+            .reset(self.config_account.epoch_count);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_spl::token::{Mint, TokenAccount};
+
+use crate::*;
+
+use effect_staking::{accounts::StakeAccount, program::EffectStaking};
+
+#[derive(Accounts)]
+pub struct EnterPool<'info> {
+    #[account(
+        mut,
+        seeds = [ b"pool", stake_mint.key().as_ref(), reward_mint.key().as_ref() ],
+        bump
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+
+    #[account(
+        has_one = authority @ RewardErrors::Unauthorized,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key(),
+        token::mint = stake_mint,
+        token::authority = stake_vault_token_account,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    /// The stake enters the pools through its reward account, which syncs them on stake changes.
+    #[account(
+        mut,
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = PoolRewardAccount::SIZE,
+        seeds = [ stake_account.key().as_ref(), reward_mint.key().as_ref() ],
+        bump,
+    )]
+    pub pool_reward_account: Account<'info, PoolRewardAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+    pub reward_mint: Account<'info, Mint>,
+    pub stake_program: Program<'info, EffectStaking>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EnterPool<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        self.reward_account.add_pool()?;
+
+        self.pool_reward_account.init(
+            self.authority.key(),
+            self.stake_account.key(),
+            self.pool_account.key(),
+        );
+        self.pool_account
+            .enter(&mut self.pool_reward_account, self.stake_account.weighted_amount)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::id::ADMIN_AUTHORITY;
 
 #[derive(Accounts)]
 pub struct Init<'info> {
@@ -22,7 +23,7 @@ pub struct Init<'info> {
     )]
     pub reward_vault_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(mut, address = ADMIN_AUTHORITY @ RewardErrors::Unauthorized)]
     pub authority: Signer<'info>,
 
     pub mint: Account<'info, Mint>,
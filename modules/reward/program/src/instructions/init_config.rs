@@ -0,0 +1,45 @@
+use crate::*;
+use anchor_spl::token::TokenAccount;
+use effect_common::id::ADMIN_AUTHORITY;
+
+#[derive(Accounts)]
+pub struct InitConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RewardConfig::SIZE,
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    /// CHECK: the reflection or pool the config administers, owned by this program.
+    #[account(owner = crate::ID @ RewardErrors::ReflectionInvalid)]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(token::mint = reward_vault_token_account.mint)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = ADMIN_AUTHORITY @ RewardErrors::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitConfig<'info> {
+    pub fn handler(&mut self, fee_basis_points: u16, fee_cap: u64, paused: bool) -> Result<()> {
+        self.config_account.set(
+            self.authority.key(),
+            self.treasury_token_account.key(),
+            fee_basis_points,
+            fee_cap,
+            paused,
+        )
+    }
+}
@@ -0,0 +1,37 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::id::ADMIN_AUTHORITY;
+
+#[derive(Accounts)]
+pub struct InitEpochVault<'info> {
+    #[account(
+        seeds = [ b"reflection", mint.key().as_ref() ],
+        bump
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = epoch_vault_token_account,
+        seeds = [ b"epoch_vault", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub epoch_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = ADMIN_AUTHORITY @ RewardErrors::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> InitEpochVault<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::id::ADMIN_AUTHORITY;
 
 #[derive(Accounts)]
 pub struct InitIntermediaryVault<'info> {
@@ -29,7 +30,7 @@ pub struct InitIntermediaryVault<'info> {
     )]
     pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
     
-    #[account(mut)]
+    #[account(mut, address = ADMIN_AUTHORITY @ RewardErrors::Unauthorized)]
     pub authority: Signer<'info>,
 
     pub mint: Account<'info, Mint>,
@@ -0,0 +1,55 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::id::ADMIN_AUTHORITY;
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = PoolAccount::SIZE,
+        seeds = [ b"pool", stake_mint.key().as_ref(), reward_mint.key().as_ref() ],
+        bump
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = reward_vault_token_account,
+        seeds = [ pool_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = reward_mint,
+        token::authority = intermediate_reward_vault_token_account,
+        seeds = [ reward_vault_token_account.key().as_ref() ],
+        bump,
+    )]
+    pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut, address = ADMIN_AUTHORITY @ RewardErrors::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub stake_mint: Account<'info, Mint>,
+
+    #[account(constraint = reward_mint.key() != stake_mint.key() @ RewardErrors::InvalidMint)]
+    pub reward_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+impl<'info> InitPool<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        self.pool_account
+            .init(self.stake_mint.key(), self.reward_mint.key());
+        Ok(())
+    }
+}
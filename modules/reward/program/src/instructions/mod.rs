@@ -5,6 +5,23 @@ pub mod init;
 pub mod sync;
 pub mod topup;
 pub mod init_intermediary_vault;
+pub mod init_pool;
+pub mod topup_pool;
+pub mod enter_pool;
+pub mod claim_pool;
+pub mod sync_pool;
+pub mod close_pool;
+pub mod configure_emission;
+pub mod compound;
+pub mod init_config;
+pub mod update_config;
+pub mod sync_many;
+pub mod pending_rewards;
+pub mod enter_epochs;
+pub mod topup_epoch;
+pub mod claim_epoch;
+pub mod init_epoch_vault;
+pub mod sweep_epoch;
 
 pub use claim::*;
 pub use close::*;
@@ -12,4 +29,21 @@ pub use enter::*;
 pub use init::*;
 pub use sync::*;
 pub use topup::*;
-pub use init_intermediary_vault::*;
\ No newline at end of file
+pub use init_intermediary_vault::*;
+pub use init_pool::*;
+pub use topup_pool::*;
+pub use enter_pool::*;
+pub use claim_pool::*;
+pub use sync_pool::*;
+pub use close_pool::*;
+pub use configure_emission::*;
+pub use compound::*;
+pub use init_config::*;
+pub use update_config::*;
+pub use sync_many::*;
+pub use pending_rewards::*;
+pub use enter_epochs::*;
+pub use topup_epoch::*;
+pub use claim_epoch::*;
+pub use init_epoch_vault::*;
+pub use sweep_epoch::*;
\ No newline at end of file
@@ -0,0 +1,67 @@
+use anchor_spl::token::TokenAccount;
+
+use crate::{effect_staking::{accounts::StakeAccount, program::EffectStaking}, *};
+
+/// The claimable rewards of a [RewardAccount], as returned by `pending_rewards`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PendingRewards {
+    pub amount: u128,
+    pub rate: u128,
+    pub weighted_amount: u128,
+    pub needs_sync: bool,
+}
+
+#[derive(Accounts)]
+pub struct GetPendingRewards<'info> {
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_account: Account<'info, RewardAccount>,
+
+    #[account(
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"reflection", stake_vault_token_account.mint.as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    pub stake_program: Program<'info, EffectStaking>,
+}
+
+impl<'info> GetPendingRewards<'info> {
+    pub fn handler(&self) -> Result<PendingRewards> {
+        // include what the emission streamed so far, without writing it
+        let mut reflection: ReflectionAccount = (*self.reflection_account).clone();
+        if !self.emission_account.data_is_empty() {
+            let data = self.emission_account.try_borrow_data()?;
+            let mut emission = EmissionAccount::try_deserialize(&mut &data[..])?;
+            emission.release(&mut reflection, Clock::get()?.unix_timestamp)?;
+        }
+
+        // a changed stake has to be synced before it can claim
+        let weighted_amount: u128 = self.stake_account.weighted_amount;
+
+        Ok(PendingRewards {
+            amount: self.reward_account.get_amount(reflection.rate),
+            rate: reflection.rate,
+            weighted_amount,
+            needs_sync: weighted_amount != self.reward_account.weighted_amount,
+        })
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_spl::token::Mint;
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct SweepEpoch<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        mut,
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    #[account(
+        mut,
+        seeds = [ b"epoch", reflection_account.key().as_ref(), &epoch.to_le_bytes() ],
+        bump,
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+}
+
+impl<'info> SweepEpoch<'info> {
+    pub fn handler(&mut self, epoch: u64) -> Result<EpochSwept> {
+        // the swept amount stays in the epoch vault and goes to the next epoch
+        let amount: u64 = self.epoch_account.sweep(Clock::get()?.unix_timestamp)?;
+        self.config_account.release_epoch_amount(amount)?;
+
+        Ok(EpochSwept {
+            reflection_account: self.reflection_account.key(),
+            epoch,
+            amount,
+        })
+    }
+}
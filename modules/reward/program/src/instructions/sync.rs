@@ -27,21 +27,32 @@ pub struct Sync<'info> {
         bump,
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
     
     pub stake_program: Program<'info, EffectStaking>
 }
 
 impl<'info> Sync<'info> {
-    pub fn handler(&mut self) -> Result<()> {
-        // decrease the reflection pool
+    pub fn handler(&mut self, pools: &[AccountInfo<'info>]) -> Result<()> {
+        release_emission!(self);
+
+        let weighted_amount: u128 = self.stake_account.weighted_amount;
         self.reflection_account
-            .remove_rewards_account(self.reward_account.reflection, self.reward_account.weighted_amount)?;
+            .sync(&mut self.reward_account, weighted_amount)?;
 
-        // re-enter the pool with the current stake
-        let amount: u128 = self.reward_account.get_amount(self.reflection_account.rate);
-        self.reward_account.update(
-            self.reflection_account.add_rewards_account(self.stake_account.weighted_amount, amount),
-            self.stake_account.weighted_amount,
+        // the pools of the stake follow its weight as well
+        sync_pools(
+            &self.stake_account.key(),
+            weighted_amount,
+            self.reward_account.pools,
+            pools,
         )
     }
 }
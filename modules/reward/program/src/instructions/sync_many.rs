@@ -0,0 +1,95 @@
+use anchor_lang::solana_program::compute_units::sol_remaining_compute_units;
+use anchor_spl::token::{Mint, TokenAccount};
+use effect_common::constants::SYNC_MIN_COMPUTE_UNITS;
+
+use crate::{effect_staking::accounts::StakeAccount, *};
+
+#[derive(Accounts)]
+pub struct SyncMany<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    pub mint: Account<'info, Mint>,
+}
+
+impl<'info> SyncMany<'info> {
+    /// Sync stakes given as `(stake_account, stake_vault_token_account, reward_account)` triples,
+    /// each followed by the `(pool_account, pool_reward_account)` pairs of the pools it entered.
+    /// Returns the number of stakes synced before the compute budget ran low.
+    pub fn handler(&mut self, accounts: &[AccountInfo<'info>]) -> Result<u32> {
+        require!(!accounts.is_empty(), RewardErrors::InvalidSyncAccounts);
+
+        release_emission!(self);
+
+        let mut synced: u32 = 0;
+        let mut remaining: &[AccountInfo<'info>] = accounts;
+
+        while !remaining.is_empty() {
+            // leave enough compute to finish, the keeper continues from the returned count
+            if sol_remaining_compute_units() < SYNC_MIN_COMPUTE_UNITS {
+                break;
+            }
+
+            let [stake_info, vault_info, reward_info, ..] = remaining else {
+                return err!(RewardErrors::InvalidSyncAccounts);
+            };
+
+            require_keys_eq!(
+                *stake_info.owner,
+                effect_staking::ID,
+                RewardErrors::InvalidSyncAccounts
+            );
+            let stake_account =
+                StakeAccount::try_deserialize(&mut &stake_info.try_borrow_data()?[..])?;
+
+            // the vault of the stake decides the reflection, like a single sync
+            let (vault_key, _) =
+                Pubkey::find_program_address(&[stake_info.key.as_ref()], &effect_staking::ID);
+            require_keys_eq!(*vault_info.key, vault_key, RewardErrors::InvalidVault);
+            let vault = TokenAccount::try_deserialize(&mut &vault_info.try_borrow_data()?[..])?;
+            require_keys_eq!(vault.mint, self.mint.key(), RewardErrors::InvalidMint);
+
+            let (reward_key, _) =
+                Pubkey::find_program_address(&[stake_info.key.as_ref()], &crate::ID);
+            require!(
+                *reward_info.key == reward_key
+                    && *reward_info.owner == crate::ID
+                    && reward_info.is_writable,
+                RewardErrors::InvalidSyncAccounts
+            );
+            let mut reward_account =
+                RewardAccount::try_deserialize(&mut &reward_info.try_borrow_data()?[..])?;
+
+            let pool_accounts: usize = 2 * reward_account.pools as usize;
+            require!(
+                remaining.len() >= 3 + pool_accounts,
+                RewardErrors::InvalidSyncAccounts
+            );
+            let (pools, next) = remaining[3..].split_at(pool_accounts);
+
+            let weighted_amount: u128 = stake_account.weighted_amount;
+            self.reflection_account.sync(&mut reward_account, weighted_amount)?;
+            reward_account.try_serialize(&mut &mut reward_info.try_borrow_mut_data()?[..])?;
+
+            // the pools of the stake follow its weight as well
+            sync_pools(stake_info.key, weighted_amount, reward_account.pools, pools)?;
+
+            remaining = next;
+            synced += 1;
+        }
+
+        Ok(synced)
+    }
+}
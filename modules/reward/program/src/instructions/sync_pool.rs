@@ -0,0 +1,92 @@
+use crate::{effect_staking::accounts::StakeAccount, *};
+
+#[derive(Accounts)]
+pub struct SyncPool<'info> {
+    /// CHECK: the stake of the pool reward account, its weight is zero once it is closed.
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        has_one = stake_account @ RewardErrors::InvalidSyncAccounts,
+    )]
+    pub pool_reward_account: Account<'info, PoolRewardAccount>,
+
+    #[account(
+        mut,
+        address = pool_reward_account.pool @ RewardErrors::InvalidSyncAccounts,
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+}
+
+impl<'info> SyncPool<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let weighted_amount: u128 = stake_weight(&self.stake_account.to_account_info())?;
+
+        self.pool_account
+            .sync(&mut self.pool_reward_account, weighted_amount)
+    }
+}
+
+/// The weight of a stake, zero once the stake is closed.
+pub fn stake_weight(stake_info: &AccountInfo) -> Result<u128> {
+    if stake_info.data_is_empty() {
+        return Ok(0);
+    }
+    require_keys_eq!(
+        *stake_info.owner,
+        effect_staking::ID,
+        RewardErrors::InvalidSyncAccounts
+    );
+
+    let stake_account = StakeAccount::try_deserialize(&mut &stake_info.try_borrow_data()?[..])?;
+    Ok(stake_account.weighted_amount)
+}
+
+/// Sync every pool a stake entered, passed as `(pool_account, pool_reward_account)` pairs.
+pub fn sync_pools(
+    stake_account: &Pubkey,
+    weighted_amount: u128,
+    pools: u8,
+    accounts: &[AccountInfo],
+) -> Result<()> {
+    require!(
+        accounts.len() == 2 * pools as usize,
+        RewardErrors::InvalidSyncAccounts
+    );
+
+    for (index, pair) in accounts.chunks(2).enumerate() {
+        let (pool_info, pool_reward_info) = (&pair[0], &pair[1]);
+
+        require!(
+            *pool_info.owner == crate::ID
+                && *pool_reward_info.owner == crate::ID
+                && pool_info.is_writable
+                && pool_reward_info.is_writable,
+            RewardErrors::InvalidSyncAccounts
+        );
+        // every pool is synced once
+        require!(
+            accounts[..2 * index]
+                .chunks(2)
+                .all(|other| other[1].key != pool_reward_info.key),
+            RewardErrors::InvalidSyncAccounts
+        );
+
+        let mut pool = PoolAccount::try_deserialize(&mut &pool_info.try_borrow_data()?[..])?;
+        let mut pool_reward =
+            PoolRewardAccount::try_deserialize(&mut &pool_reward_info.try_borrow_data()?[..])?;
+        require_keys_eq!(
+            pool_reward.stake_account,
+            *stake_account,
+            RewardErrors::InvalidSyncAccounts
+        );
+        require_keys_eq!(pool_reward.pool, *pool_info.key, RewardErrors::InvalidSyncAccounts);
+
+        pool.sync(&mut pool_reward, weighted_amount)?;
+
+        pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+        pool_reward.try_serialize(&mut &mut pool_reward_info.try_borrow_mut_data()?[..])?;
+    }
+
+    Ok(())
+}
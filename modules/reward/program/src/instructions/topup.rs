@@ -2,6 +2,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 
 use crate::*;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Topup<'info> {
     #[account(mut)]
@@ -14,6 +15,21 @@ pub struct Topup<'info> {
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
 
+    /// CHECK: the emission schedule of the reflection, empty when rewards are not streamed.
+    #[account(
+        mut,
+        seeds = [ b"emission", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+        constraint = !config_account.paused @ RewardErrors::Paused,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
     #[account(
         mut,
         token::mint = mint,
@@ -32,16 +48,23 @@ pub struct Topup<'info> {
     )]
     pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        address = config_account.treasury @ RewardErrors::InvalidTreasury,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
     pub token_program: Program<'info, Token>,
 }
 
 impl<'info> Topup<'info> {
-    pub fn handler(&mut self) -> Result<()> {
-        let amount: u64 = self
-            .intermediate_reward_vault_token_account
-            .amount
-            .try_into()
-            .unwrap();
+    pub fn handler(&mut self) -> Result<RewardsToppedUp> {
+        // the due emission is reflected before the reflection changes
+        release_emission!(self);
+
+        let balance: u64 = self.intermediate_reward_vault_token_account.amount;
+        let fee: u64 = pay_protocol_fee!(self, balance);
+        let amount: u64 = balance - fee;
 
         // transfer to reward vault
         transfer_tokens_from_vault!(
@@ -55,8 +78,29 @@ impl<'info> Topup<'info> {
             amount
         )?;
 
-        self.reflection_account.topup(amount.try_into().unwrap())?;
+        // with an emission schedule the topup is streamed instead of reflected at once
+        let emission_info = self.emission_account.to_account_info();
+        let mut emission: Option<EmissionAccount> = if emission_info.data_is_empty() {
+            None
+        } else {
+            Some(EmissionAccount::try_deserialize(
+                &mut &emission_info.try_borrow_data()?[..],
+            )?)
+        };
+
+        match emission.as_mut().filter(|emission| emission.rate > 0) {
+            Some(emission) => {
+                emission.extend(amount, Clock::get()?.unix_timestamp)?;
+                emission.try_serialize(&mut &mut emission_info.try_borrow_mut_data()?[..])?;
+            }
+            None => self.reflection_account.topup(amount.into())?,
+        }
 
-        Ok(())
+        Ok(RewardsToppedUp {
+            reflection_account: self.reflection_account.key(),
+            amount,
+            fee,
+            treasury: self.treasury_token_account.key(),
+        })
     }
 }
@@ -0,0 +1,122 @@
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::*;
+use effect_common::cpi;
+use effect_staking::{accounts::TotalCheckpointAccount, program::EffectStaking};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TopupEpoch<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        mut,
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized,
+        constraint = !config_account.paused @ RewardErrors::Paused,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = EpochAccount::SIZE,
+        seeds = [
+            b"epoch",
+            reflection_account.key().as_ref(),
+            &config_account.epoch_count.to_le_bytes()
+        ],
+        bump,
+    )]
+    pub epoch_account: Account<'info, EpochAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        seeds = [ b"epoch_vault", reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub epoch_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [ b"checkpoints" ],
+        bump,
+        seeds::program = stake_program.key()
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        address = config_account.treasury @ RewardErrors::InvalidTreasury,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub stake_program: Program<'info, EffectStaking>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TopupEpoch<'info> {
+    pub fn handler(&mut self) -> Result<EpochOpened> {
+        // the epoch vault also holds what earlier epochs did not pay out yet
+        let balance: u64 = self
+            .epoch_vault_token_account
+            .amount
+            .checked_sub(self.config_account.epoch_reserved_amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        let fee: u64 = self.config_account.fee(balance);
+        let amount: u64 = balance - fee;
+
+        if fee > 0 {
+            transfer_tokens_from_vault!(
+                self,
+                epoch_vault_token_account,
+                treasury_token_account,
+                &[epoch_vault_seed!(self.reflection_account.key().as_ref())],
+                fee
+            )?;
+        }
+
+        let epoch: u64 = self.config_account.epoch_count;
+        self.epoch_account.set_inner(EpochAccount {
+            reflection: self.reflection_account.key(),
+            epoch,
+            amount,
+            total_weighted_amount: 0,
+            claimed_amount: 0,
+            swept_amount: 0,
+            open_time: Clock::get()?.unix_timestamp,
+        });
+
+        // snapshot the total stake weight, an epoch without stakes could never be claimed
+        let total_checkpoint_account = self.total_checkpoint_account.load()?;
+        let total_weighted_amount: u64 = checkpoint_weighted_amount_at(
+            total_checkpoint_account.count,
+            &total_checkpoint_account.checkpoints,
+            self.epoch_account.snapshot_time(),
+        )
+        .unwrap_or(0);
+        require!(total_weighted_amount > 0, RewardErrors::InvalidEpoch);
+
+        self.epoch_account.total_weighted_amount = total_weighted_amount;
+        self.config_account.open_epoch(amount)?;
+
+        Ok(EpochOpened {
+            reflection_account: self.reflection_account.key(),
+            epoch,
+            amount,
+            fee,
+            total_weighted_amount,
+        })
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TopupPool<'info> {
+    pub stake_mint: Account<'info, Mint>,
+
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", stake_mint.key().as_ref(), reward_mint.key().as_ref()],
+        bump,
+    )]
+    pub pool_account: Account<'info, PoolAccount>,
+
+    #[account(
+        seeds = [ b"config", pool_account.key().as_ref() ],
+        bump,
+        constraint = !config_account.paused @ RewardErrors::Paused,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = reward_vault_token_account,
+        seeds = [ pool_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = intermediate_reward_vault_token_account,
+        seeds = [ reward_vault_token_account.key().as_ref() ],
+        bump,
+    )]
+    pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = config_account.treasury @ RewardErrors::InvalidTreasury,
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> TopupPool<'info> {
+    pub fn handler(&mut self) -> Result<RewardsToppedUp> {
+        let balance: u64 = self.intermediate_reward_vault_token_account.amount;
+        let fee: u64 = pay_protocol_fee!(self, balance);
+        let amount: u64 = balance - fee;
+
+        // transfer to reward vault
+        transfer_tokens_from_vault!(
+            self,
+            intermediate_reward_vault_token_account,
+            reward_vault_token_account,
+            &[intermediary_vault_seed!(self.reward_vault_token_account.key().as_ref())],
+            amount
+        )?;
+
+        self.pool_account.distribute(amount)?;
+
+        Ok(RewardsToppedUp {
+            reflection_account: self.pool_account.key(),
+            amount,
+            fee,
+            treasury: self.treasury_token_account.key(),
+        })
+    }
+}
@@ -0,0 +1,43 @@
+use crate::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [ b"config", reflection_account.key().as_ref() ],
+        bump,
+        has_one = authority @ RewardErrors::Unauthorized,
+    )]
+    pub config_account: Account<'info, RewardConfig>,
+
+    /// CHECK: the reflection or pool the config administers, owned by this program.
+    #[account(owner = crate::ID @ RewardErrors::ReflectionInvalid)]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [ reflection_account.key().as_ref() ],
+        bump,
+    )]
+    pub reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(token::mint = reward_vault_token_account.mint)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: any account can become the new config authority.
+    pub new_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn handler(&mut self, fee_basis_points: u16, fee_cap: u64, paused: bool) -> Result<()> {
+        self.config_account.set(
+            self.new_authority.key(),
+            self.treasury_token_account.key(),
+            fee_basis_points,
+            fee_cap,
+            paused,
+        )
+    }
+}
@@ -1,4 +1,5 @@
 mod errors;
+mod events;
 mod instructions;
 mod macros;
 mod security;
@@ -10,6 +11,7 @@ use effect_common::*;
 use instructions::*;
 
 pub use errors::*;
+pub use events::*;
 pub use state::*;
 
 inject_declare_id_output!("../../../target/deploy/effect_reward-keypair.json");
@@ -29,9 +31,12 @@ pub mod effect_reward {
         ctx.accounts.handler()
     }
 
-    /// Topup the [ReflectionAccount](#reflection-account) and [VaultAccount](#vault_token_account-account).
+    /// Topup the [ReflectionAccount](#reflection-account) and [VaultAccount](#vault_token_account-account),
+    /// streaming the topup through the emission schedule when the pool has one.
     pub fn topup(ctx: Context<Topup>) -> Result<()> {
-        ctx.accounts.handler()
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
     }
 
     /// Initialize a [RewardsAccount](#rewards-account).
@@ -45,12 +50,125 @@ pub mod effect_reward {
     }
 
     /// Re-calculate reflection points.
-    pub fn sync(ctx: Context<Sync>) -> Result<()> {
-        ctx.accounts.handler()
+    /// The pools the stake entered are passed as pairs in the remaining accounts.
+    pub fn sync<'info>(ctx: Context<'_, '_, '_, 'info, Sync<'info>>) -> Result<()> {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    /// Re-calculate reflection points of many stakes and their pools, passed in the remaining
+    /// accounts.
+    pub fn sync_many<'info>(ctx: Context<'_, '_, '_, 'info, SyncMany<'info>>) -> Result<u32> {
+        ctx.accounts.handler(ctx.remaining_accounts)
     }
 
     /// Close a [RewardsAccount](#rewards-account).
     pub fn close(ctx: Context<Close>) -> Result<()> {
         ctx.accounts.handler()
     }
+
+    /// Initialize a [PoolAccount](#pool-account) paying `reward_mint` to stakers of `stake_mint`.
+    pub fn init_pool(ctx: Context<InitPool>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Topup a reward pool from its intermediary vault.
+    pub fn topup_pool(ctx: Context<TopupPool>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Initialize a [PoolRewardAccount](#pool-reward-account), synced along with the
+    /// [RewardsAccount](#rewards-account) of the stake from then on.
+    pub fn enter_pool(ctx: Context<EnterPool>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Claim rewards from a reward pool in its reward mint.
+    pub fn claim_pool(ctx: Context<ClaimPool>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Move a [PoolRewardAccount](#pool-reward-account) to the current weight of its stake.
+    pub fn sync_pool(ctx: Context<SyncPool>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Close a [PoolRewardAccount](#pool-reward-account), also once its stake is closed.
+    pub fn close_pool(ctx: Context<ClosePool>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Stream the intermediary vault into the reflection at `rate` per second until `end_time`.
+    pub fn configure_emission(
+        ctx: Context<ConfigureEmission>,
+        rate: u64,
+        end_time: i64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(rate, end_time)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Compound the rewards of a [RewardsAccount](#rewards-account) into its stake.
+    /// The pools the stake entered are passed as pairs in the remaining accounts.
+    pub fn compound<'info>(ctx: Context<'_, '_, '_, 'info, Compound<'info>>) -> Result<()> {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    /// Initialize the [RewardConfig](#reward-config) of a reward pool.
+    pub fn init_config(
+        ctx: Context<InitConfig>,
+        fee_basis_points: u16,
+        fee_cap: u64,
+        paused: bool,
+    ) -> Result<()> {
+        ctx.accounts.handler(fee_basis_points, fee_cap, paused)
+    }
+
+    /// Update the [RewardConfig](#reward-config) of a reward pool, rotating its authority.
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        fee_basis_points: u16,
+        fee_cap: u64,
+        paused: bool,
+    ) -> Result<()> {
+        ctx.accounts.handler(fee_basis_points, fee_cap, paused)
+    }
+
+    /// Get the claimable rewards of a [RewardsAccount](#rewards-account) and if it needs a sync.
+    pub fn pending_rewards(ctx: Context<GetPendingRewards>) -> Result<PendingRewards> {
+        ctx.accounts.handler()
+    }
+
+    /// Take part in the reward epochs with a [RewardsAccount](#rewards-account).
+    pub fn enter_epochs(ctx: Context<EnterEpochs>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Initialize the vault that funds the reward epochs of a reward pool.
+    pub fn init_epoch_vault(ctx: Context<InitEpochVault>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Open a reward epoch with the unreserved epoch vault, snapshotting the total stake weight.
+    pub fn topup_epoch(ctx: Context<TopupEpoch>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Claim the share of a stake in a reward epoch, by its weight at the snapshot.
+    pub fn claim_epoch(ctx: Context<ClaimEpoch>, epoch: u64) -> Result<()> {
+        let event = ctx.accounts.handler(epoch)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Release the unclaimed part of an expired reward epoch to the next epoch.
+    pub fn sweep_epoch(ctx: Context<SweepEpoch>, epoch: u64) -> Result<()> {
+        let event = ctx.accounts.handler(epoch)?;
+        emit_cpi!(event);
+        Ok(())
+    }
 }
@@ -13,3 +13,46 @@ macro_rules! intermediary_vault_seed {
         &[$reflection_vault_key.as_ref(), &[bump]][..]
     }};
 }
+
+#[macro_export]
+macro_rules! epoch_vault_seed {
+    ($reflection_key:expr) => {{
+        let bump = Pubkey::find_program_address(&[b"epoch_vault", $reflection_key], &id()).1;
+        &[b"epoch_vault".as_ref(), $reflection_key.as_ref(), &[bump]][..]
+    }};
+}
+
+/// Stream the pending emission into the reflection, if the pool has an emission schedule.
+#[macro_export]
+macro_rules! release_emission {
+    ($accounts:expr) => {{
+        let emission_info = $accounts.emission_account.to_account_info();
+
+        if !emission_info.data_is_empty() {
+            let mut emission =
+                EmissionAccount::try_deserialize(&mut &emission_info.try_borrow_data()?[..])?;
+            emission.release(&mut $accounts.reflection_account, Clock::get()?.unix_timestamp)?;
+            emission.try_serialize(&mut &mut emission_info.try_borrow_mut_data()?[..])?;
+        }
+    }};
+}
+
+/// Route the protocol fee of a topup from the intermediary vault to the treasury.
+#[macro_export]
+macro_rules! pay_protocol_fee {
+    ($accounts:expr, $amount:expr) => {{
+        let fee: u64 = $accounts.config_account.fee($amount);
+
+        if fee > 0 {
+            transfer_tokens_from_vault!(
+                $accounts,
+                intermediate_reward_vault_token_account,
+                treasury_token_account,
+                &[intermediary_vault_seed!($accounts.reward_vault_token_account.key().as_ref())],
+                fee
+            )?;
+        }
+
+        fee
+    }};
+}
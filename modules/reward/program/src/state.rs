@@ -1,6 +1,9 @@
 use anchor_lang::prelude::*;
 
-use crate::RewardErrors;
+use crate::{effect_staking::types::Checkpoint, RewardErrors};
+use effect_common::constants::{
+    BASIS_POINTS, EPOCH_CLAIM_DAYS, LOCK_TIER_MULTIPLIER_MAX, SECONDS_PER_DAY,
+};
 /***
  * Accounts
  */
@@ -17,8 +20,10 @@ impl ReflectionAccount {
     pub const SIZE: usize = 8 + std::mem::size_of::<ReflectionAccount>();
 
     pub fn init(&mut self, total_supply: u64) -> Result<()> {
-        // set initial rate based on total supply of the given mint.
-        self.rate = (u128::MAX - (u128::MAX % total_supply as u128)) / total_supply as u128;
+        // set initial rate based on the largest weight the total supply of the given mint can have
+        let max_weighted_amount: u128 =
+            total_supply as u128 * LOCK_TIER_MULTIPLIER_MAX as u128 / BASIS_POINTS as u128;
+        self.rate = u128::MAX / max_weighted_amount;
         self.total_reflection = 0;
         self.total_weighted_amount = 0;
 
@@ -31,19 +36,31 @@ impl ReflectionAccount {
             return Err(RewardErrors::ReflectionInvalid.into());
         }
 
-        self.total_weighted_amount += weighted_amount;
+        self.total_weighted_amount = self
+            .total_weighted_amount
+            .checked_add(weighted_amount)
+            .ok_or(RewardErrors::MathOverflow)?;
         self.rate = self.total_reflection / self.total_weighted_amount;
 
         Ok(())
     }
 
-    pub fn add_rewards_account(&mut self, xefx: u128, weighted_amount: u128) -> u128 {
-        let reflection: u128 = (xefx + weighted_amount) * self.rate;
+    pub fn add_rewards_account(&mut self, xefx: u128, weighted_amount: u128) -> Result<u128> {
+        let reflection: u128 = xefx
+            .checked_add(weighted_amount)
+            .and_then(|amount| amount.checked_mul(self.rate))
+            .ok_or(RewardErrors::MathOverflow)?;
 
-        self.total_reflection += reflection;
-        self.total_weighted_amount += xefx;
+        self.total_reflection = self
+            .total_reflection
+            .checked_add(reflection)
+            .ok_or(RewardErrors::MathOverflow)?;
+        self.total_weighted_amount = self
+            .total_weighted_amount
+            .checked_add(xefx)
+            .ok_or(RewardErrors::MathOverflow)?;
 
-        reflection
+        Ok(reflection)
     }
 
     pub fn remove_rewards_account(
@@ -51,10 +68,43 @@ impl ReflectionAccount {
         reflection: u128,
         weighted_amount: u128,
     ) -> Result<()> {
-        self.total_weighted_amount -= weighted_amount;
-        self.total_reflection -= reflection;
+        self.total_weighted_amount = self
+            .total_weighted_amount
+            .checked_sub(weighted_amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        self.total_reflection = self
+            .total_reflection
+            .checked_sub(reflection)
+            .ok_or(RewardErrors::MathOverflow)?;
         Ok(())
     }
+
+    /// Re-enter a rewards account with the current weight of its stake, keeping pending rewards.
+    pub fn sync_rewards_account(
+        &mut self,
+        reward_account: &mut RewardAccount,
+        weighted_amount: u128,
+    ) -> Result<()> {
+        // decrease the reflection pool
+        self.remove_rewards_account(reward_account.reflection, reward_account.weighted_amount)?;
+
+        // re-enter the pool with the current stake
+        let amount: u128 = reward_account.get_amount(self.rate);
+        reward_account.update(self.add_rewards_account(weighted_amount, amount)?, weighted_amount)
+    }
+
+    /// Remove a rewards account and its pending rewards, returning the pending amount.
+    pub fn settle_rewards_account(&mut self, reward_account: &RewardAccount) -> Result<u128> {
+        let amount: u128 = reward_account.get_amount(self.rate);
+
+        let weighted_amount: u128 = reward_account
+            .weighted_amount
+            .checked_add(amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        self.remove_rewards_account(reward_account.reflection, weighted_amount)?;
+
+        Ok(amount)
+    }
 }
 
 #[account]
@@ -62,6 +112,7 @@ pub struct RewardAccount {
     pub authority: Pubkey,
     pub reflection: u128,
     pub weighted_amount: u128,
+    pub pools: u8,
 }
 
 impl RewardAccount {
@@ -80,7 +131,1092 @@ impl RewardAccount {
         Ok(())
     }
 
-    pub fn get_amount(&mut self, rate: u128) -> u128 {
+    pub fn get_amount(&self, rate: u128) -> u128 {
         self.reflection / rate - self.weighted_amount
     }
+
+    /// Count a pool the stake entered, pools are synced along with this account.
+    pub fn add_pool(&mut self) -> Result<()> {
+        self.pools = self.pools.checked_add(1).ok_or(RewardErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove_pool(&mut self) -> Result<()> {
+        self.pools = self.pools.checked_sub(1).ok_or(RewardErrors::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// A reward pool paying out its stakers by weight. It is implemented by the compounding
+/// [ReflectionAccount] and by the [PoolAccount] of other reward mints, so both share the same
+/// instructions.
+pub trait RewardPool {
+    type Account;
+
+    /// Distribute `amount` over the weight in the pool.
+    fn distribute(&mut self, amount: u64) -> Result<()>;
+
+    /// Enter an account into the pool with `weighted_amount`.
+    fn enter(&mut self, account: &mut Self::Account, weighted_amount: u128) -> Result<()>;
+
+    /// The rewards an account can claim.
+    fn pending(&self, account: &Self::Account) -> Result<u128>;
+
+    /// Move an account to `weighted_amount`, keeping its pending rewards.
+    fn sync(&mut self, account: &mut Self::Account, weighted_amount: u128) -> Result<()>;
+
+    /// Take the pending rewards of an account and re-enter it with `weighted_amount`.
+    fn claim(&mut self, account: &mut Self::Account, weighted_amount: u128) -> Result<u128>;
+
+    /// Remove an account from the pool, returning its pending rewards.
+    fn settle(&mut self, account: &Self::Account) -> Result<u128>;
+}
+
+impl RewardPool for ReflectionAccount {
+    type Account = RewardAccount;
+
+    fn distribute(&mut self, amount: u64) -> Result<()> {
+        self.topup(amount.into())
+    }
+
+    fn enter(&mut self, account: &mut RewardAccount, weighted_amount: u128) -> Result<()> {
+        account.update(self.add_rewards_account(weighted_amount, 0)?, weighted_amount)
+    }
+
+    fn pending(&self, account: &RewardAccount) -> Result<u128> {
+        Ok(account.get_amount(self.rate))
+    }
+
+    fn sync(&mut self, account: &mut RewardAccount, weighted_amount: u128) -> Result<()> {
+        self.sync_rewards_account(account, weighted_amount)
+    }
+
+    fn claim(&mut self, account: &mut RewardAccount, weighted_amount: u128) -> Result<u128> {
+        let amount: u128 = self.settle_rewards_account(account)?;
+        self.enter(account, weighted_amount)?;
+        Ok(amount)
+    }
+
+    fn settle(&mut self, account: &RewardAccount) -> Result<u128> {
+        self.settle_rewards_account(account)
+    }
+}
+
+/// The precision of the reward index of a [PoolAccount].
+pub const POOL_INDEX_PRECISION: u128 = 1_000_000_000_000;
+
+/// The `PoolAccount` pays out a reward mint to stakers of a stake mint, pro-rata to their weight.
+/// Unlike the reflection it does not compound, the index only grows with the stake weight.
+#[account]
+pub struct PoolAccount {
+    pub stake_mint: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_index: u128,
+    pub total_weighted_amount: u128,
+}
+
+impl PoolAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<PoolAccount>();
+
+    pub fn init(&mut self, stake_mint: Pubkey, reward_mint: Pubkey) {
+        self.stake_mint = stake_mint;
+        self.reward_mint = reward_mint;
+        self.reward_index = 0;
+        self.total_weighted_amount = 0;
+    }
+
+    fn add_weight(&mut self, weighted_amount: u128) -> Result<()> {
+        self.total_weighted_amount = self
+            .total_weighted_amount
+            .checked_add(weighted_amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    fn remove_weight(&mut self, weighted_amount: u128) -> Result<()> {
+        self.total_weighted_amount = self
+            .total_weighted_amount
+            .checked_sub(weighted_amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        Ok(())
+    }
+}
+
+impl RewardPool for PoolAccount {
+    type Account = PoolRewardAccount;
+
+    fn distribute(&mut self, amount: u64) -> Result<()> {
+        // dont allow a topup while there is no weight to distribute over
+        require!(self.total_weighted_amount > 0, RewardErrors::ReflectionInvalid);
+
+        self.reward_index = (amount as u128)
+            .checked_mul(POOL_INDEX_PRECISION)
+            .map(|amount| amount / self.total_weighted_amount)
+            .and_then(|increase| self.reward_index.checked_add(increase))
+            .ok_or(RewardErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    fn enter(&mut self, account: &mut PoolRewardAccount, weighted_amount: u128) -> Result<()> {
+        self.add_weight(weighted_amount)?;
+        account.reward_index = self.reward_index;
+        account.weighted_amount = weighted_amount;
+        Ok(())
+    }
+
+    fn pending(&self, account: &PoolRewardAccount) -> Result<u128> {
+        (self.reward_index - account.reward_index)
+            .checked_mul(account.weighted_amount)
+            .map(|accrued| accrued / POOL_INDEX_PRECISION)
+            .and_then(|accrued| accrued.checked_add(account.pending_amount))
+            .ok_or(RewardErrors::MathOverflow.into())
+    }
+
+    fn sync(&mut self, account: &mut PoolRewardAccount, weighted_amount: u128) -> Result<()> {
+        account.pending_amount = self.pending(account)?;
+        self.remove_weight(account.weighted_amount)?;
+        self.enter(account, weighted_amount)
+    }
+
+    fn claim(&mut self, account: &mut PoolRewardAccount, weighted_amount: u128) -> Result<u128> {
+        self.sync(account, weighted_amount)?;
+        Ok(std::mem::take(&mut account.pending_amount))
+    }
+
+    fn settle(&mut self, account: &PoolRewardAccount) -> Result<u128> {
+        let amount: u128 = self.pending(account)?;
+        self.remove_weight(account.weighted_amount)?;
+        Ok(amount)
+    }
+}
+
+/// The `PoolRewardAccount` holds the weight of a stake in a [PoolAccount].
+#[account]
+pub struct PoolRewardAccount {
+    pub authority: Pubkey,
+    pub stake_account: Pubkey,
+    pub pool: Pubkey,
+    pub weighted_amount: u128,
+    pub reward_index: u128,
+    pub pending_amount: u128,
+}
+
+impl PoolRewardAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<PoolRewardAccount>();
+
+    pub fn init(&mut self, authority: Pubkey, stake_account: Pubkey, pool: Pubkey) {
+        self.authority = authority;
+        self.stake_account = stake_account;
+        self.pool = pool;
+        self.weighted_amount = 0;
+        self.reward_index = 0;
+        self.pending_amount = 0;
+    }
+}
+
+/// The `RewardConfig` holds the administration of a reward pool.
+#[account]
+pub struct RewardConfig {
+    pub authority: Pubkey,
+    pub treasury: Pubkey,
+    pub fee_basis_points: u16,
+    pub fee_cap: u64,
+    pub paused: bool,
+    pub epoch_count: u64,
+    pub epoch_reserved_amount: u64,
+}
+
+impl RewardConfig {
+    pub const SIZE: usize = 8 + std::mem::size_of::<RewardConfig>();
+
+    pub fn set(
+        &mut self,
+        authority: Pubkey,
+        treasury: Pubkey,
+        fee_basis_points: u16,
+        fee_cap: u64,
+        paused: bool,
+    ) -> Result<()> {
+        require!(fee_basis_points as u64 <= BASIS_POINTS, RewardErrors::InvalidFee);
+
+        self.authority = authority;
+        self.treasury = treasury;
+        self.fee_basis_points = fee_basis_points;
+        self.fee_cap = fee_cap;
+        self.paused = paused;
+        Ok(())
+    }
+
+    /// The protocol fee on a topup of `amount`, capped at `fee_cap`.
+    pub fn fee(&self, amount: u64) -> u64 {
+        let fee = amount as u128 * self.fee_basis_points as u128 / BASIS_POINTS as u128;
+        (fee as u64).min(self.fee_cap)
+    }
+
+    /// Open the next epoch, reserving its `amount` in the epoch vault.
+    pub fn open_epoch(&mut self, amount: u64) -> Result<()> {
+        let epoch_reserved_amount: u64 = self
+            .epoch_reserved_amount
+            .checked_add(amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        self.epoch_count = self
+            .epoch_count
+            .checked_add(1)
+            .ok_or(RewardErrors::MathOverflow)?;
+        self.epoch_reserved_amount = epoch_reserved_amount;
+        Ok(())
+    }
+
+    /// Release `amount` of the epoch vault once it is claimed or swept.
+    pub fn release_epoch_amount(&mut self, amount: u64) -> Result<()> {
+        self.epoch_reserved_amount = self
+            .epoch_reserved_amount
+            .checked_sub(amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// The `EmissionAccount` streams rewards into a reflection at a fixed rate until `end_time`.
+#[account]
+pub struct EmissionAccount {
+    pub reflection: Pubkey,
+    pub rate: u64,
+    pub end_time: i64,
+    pub last_update_time: i64,
+    pub remaining_amount: u64,
+}
+
+impl EmissionAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<EmissionAccount>();
+
+    pub fn configure(
+        &mut self,
+        reflection: Pubkey,
+        rate: u64,
+        end_time: i64,
+        now: i64,
+        amount: u64,
+    ) -> Result<()> {
+        require!(end_time > now, RewardErrors::InvalidEmission);
+
+        self.reflection = reflection;
+        self.rate = rate;
+        self.end_time = end_time;
+        self.last_update_time = now;
+        self.remaining_amount = self
+            .remaining_amount
+            .checked_add(amount)
+            .ok_or(RewardErrors::InvalidEmission)?;
+
+        Ok(())
+    }
+
+    /// Release the amount emitted since the last update, bounded by what is left to stream.
+    /// Once the stream ended everything left is due, including what was kept while the pool
+    /// was empty.
+    pub fn accrue(&mut self, now: i64) -> u64 {
+        let until = now.min(self.end_time);
+
+        let amount = if now >= self.end_time {
+            self.remaining_amount
+        } else if until > self.last_update_time {
+            let elapsed = (until - self.last_update_time) as u64;
+            self.rate.saturating_mul(elapsed).min(self.remaining_amount)
+        } else {
+            0
+        };
+
+        self.last_update_time = self.last_update_time.max(until);
+        self.remaining_amount -= amount;
+
+        amount
+    }
+
+    /// Stream `amount` more at the current rate, restarting the stream once it ended.
+    pub fn extend(&mut self, amount: u64, now: i64) -> Result<()> {
+        require!(self.rate > 0, RewardErrors::InvalidEmission);
+
+        if self.end_time <= now {
+            self.end_time = now;
+            self.last_update_time = now;
+        }
+
+        let duration: u64 = amount.div_ceil(self.rate);
+        self.end_time = i64::try_from(duration)
+            .ok()
+            .and_then(|duration| self.end_time.checked_add(duration))
+            .ok_or(RewardErrors::InvalidEmission)?;
+        self.remaining_amount = self
+            .remaining_amount
+            .checked_add(amount)
+            .ok_or(RewardErrors::InvalidEmission)?;
+
+        Ok(())
+    }
+
+    /// Reflect the emitted amount, the emission is kept for later while the pool is empty.
+    pub fn release(&mut self, reflection: &mut ReflectionAccount, now: i64) -> Result<()> {
+        let amount = self.accrue(now);
+
+        if amount == 0 {
+            return Ok(());
+        }
+
+        if reflection.total_reflection == 0 {
+            self.remaining_amount += amount;
+            return Ok(());
+        }
+
+        reflection.topup(amount.into())
+    }
+}
+
+/// The number of 64-epoch words in the claim bitmap of an [EpochClaimAccount].
+pub const EPOCH_CLAIM_WORDS: usize = 4;
+
+/// The weight of a staking checkpoint ring buffer at or before `timestamp`, if still kept.
+pub fn checkpoint_weighted_amount_at(
+    count: u64,
+    checkpoints: &[Checkpoint],
+    timestamp: i64,
+) -> Option<u64> {
+    let size = checkpoints.len() as u64;
+
+    // walk back from the newest checkpoint, like the staking program does
+    (count.saturating_sub(size)..count)
+        .rev()
+        .map(|index| &checkpoints[(index % size) as usize])
+        .find(|checkpoint| checkpoint.timestamp <= timestamp)
+        .map(|checkpoint| checkpoint.weighted_amount)
+}
+
+/// The `EpochAccount` records a topup distributed over the stake weights at its snapshot.
+#[account]
+pub struct EpochAccount {
+    pub reflection: Pubkey,
+    pub epoch: u64,
+    pub amount: u64,
+    pub total_weighted_amount: u64,
+    pub claimed_amount: u64,
+    pub swept_amount: u64,
+    pub open_time: i64,
+}
+
+impl EpochAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<EpochAccount>();
+
+    /// The stake weights are read the second before the epoch opened, which no stake can
+    /// change anymore.
+    pub fn snapshot_time(&self) -> i64 {
+        self.open_time - 1
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.open_time + (EPOCH_CLAIM_DAYS as u128 * SECONDS_PER_DAY) as i64
+    }
+
+    /// The part of the epoch that is neither claimed nor swept.
+    pub fn unclaimed_amount(&self) -> u64 {
+        self.amount - self.claimed_amount - self.swept_amount
+    }
+
+    /// The share of the epoch for a stake of `weighted_amount` at the snapshot.
+    pub fn share(&self, weighted_amount: u64) -> u64 {
+        if self.total_weighted_amount == 0 {
+            return 0;
+        }
+
+        // bounded by the epoch, should the stake history ever disagree with the total
+        (self.amount as u128 * weighted_amount as u128 / self.total_weighted_amount as u128)
+            .min(self.unclaimed_amount() as u128) as u64
+    }
+
+    /// Claim the share of a stake of `weighted_amount` at the snapshot, until the epoch expires.
+    pub fn claim(&mut self, weighted_amount: u64, now: i64) -> Result<u64> {
+        require!(!self.is_expired(now), RewardErrors::InvalidEpoch);
+
+        let share: u64 = self.share(weighted_amount);
+        self.claimed_amount = self
+            .claimed_amount
+            .checked_add(share)
+            .ok_or(RewardErrors::MathOverflow)?;
+        Ok(share)
+    }
+
+    /// Release what was not claimed once the epoch expired, for a later epoch to pay out.
+    pub fn sweep(&mut self, now: i64) -> Result<u64> {
+        require!(self.is_expired(now), RewardErrors::EpochNotExpired);
+
+        let amount: u64 = self.unclaimed_amount();
+        self.swept_amount = self
+            .swept_amount
+            .checked_add(amount)
+            .ok_or(RewardErrors::MathOverflow)?;
+        Ok(amount)
+    }
+}
+
+/// The `EpochClaimAccount` holds the epochs a [RewardAccount] claimed.
+#[account]
+pub struct EpochClaimAccount {
+    pub reward_account: Pubkey,
+    pub start_epoch: u64,
+    pub claimed: [u64; EPOCH_CLAIM_WORDS],
+}
+
+impl EpochClaimAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<EpochClaimAccount>();
+    const WINDOW: u64 = 64 * EPOCH_CLAIM_WORDS as u64;
+
+    /// Take part in the epochs opened from `epoch_count` on.
+    pub fn reset(&mut self, epoch_count: u64) {
+        self.start_epoch = epoch_count;
+        self.claimed = [0; EPOCH_CLAIM_WORDS];
+    }
+
+    pub fn is_claimed(&self, epoch: u64) -> bool {
+        if epoch < self.start_epoch {
+            return true;
+        }
+
+        let offset = epoch - self.start_epoch;
+        offset < Self::WINDOW && self.claimed[(offset / 64) as usize] & (1 << (offset % 64)) != 0
+    }
+
+    /// Mark `epoch` as claimed. Claiming past the window drops its oldest words, whose epochs
+    /// can not be claimed anymore.
+    pub fn claim(&mut self, epoch: u64) -> Result<()> {
+        require!(epoch >= self.start_epoch, RewardErrors::InvalidEpoch);
+
+        let offset = epoch - self.start_epoch;
+        if offset >= Self::WINDOW {
+            self.slide((offset - Self::WINDOW) / 64 + 1);
+        }
+        require!(!self.is_claimed(epoch), RewardErrors::EpochClaimed);
+
+        let offset = epoch - self.start_epoch;
+        self.claimed[(offset / 64) as usize] |= 1 << (offset % 64);
+
+        // slide the window past fully claimed words
+        while self.claimed[0] == u64::MAX {
+            self.slide(1);
+        }
+
+        Ok(())
+    }
+
+    fn slide(&mut self, words: u64) {
+        let dropped = (words as usize).min(EPOCH_CLAIM_WORDS);
+
+        self.claimed.rotate_left(dropped);
+        self.claimed[EPOCH_CLAIM_WORDS - dropped..].fill(0);
+        self.start_epoch += 64 * words;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const SUPPLY: u64 = 5_000_000_000_000_000;
+
+    fn pool() -> ReflectionAccount {
+        let mut reflection = ReflectionAccount {
+            rate: 0,
+            total_reflection: 0,
+            total_weighted_amount: 0,
+        };
+        reflection.init(SUPPLY).unwrap();
+        reflection
+    }
+
+    #[test]
+    fn top_tier_stakes_at_max_supply_do_not_overflow() {
+        for supply in [SUPPLY, u64::MAX] {
+            let mut reflection = ReflectionAccount {
+                rate: 0,
+                total_reflection: 0,
+                total_weighted_amount: 0,
+            };
+            reflection.init(supply).unwrap();
+
+            // the whole supply is staked in the top tier, spread over a few stakes
+            let weighted_amount: u128 =
+                supply as u128 * LOCK_TIER_MULTIPLIER_MAX as u128 / BASIS_POINTS as u128;
+            let mut accounts: Vec<RewardAccount> = (0..4)
+                .map(|_| enter(&mut reflection, weighted_amount / 4))
+                .collect();
+            assert_eq!(reflection.total_weighted_amount, weighted_amount / 4 * 4);
+
+            reflection.topup(1_000_000_000).unwrap();
+            for account in accounts.iter_mut() {
+                let amount = claim(&mut reflection, account);
+                assert!(amount.abs_diff(250_000_000) <= 1);
+            }
+
+            // any weight beyond the largest multiplier is rejected instead of overflowing
+            assert_eq!(
+                reflection.add_rewards_account(u128::MAX / reflection.rate + 1, 0).unwrap_err(),
+                RewardErrors::MathOverflow.into()
+            );
+        }
+    }
+
+    fn enter(reflection: &mut ReflectionAccount, weighted_amount: u128) -> RewardAccount {
+        RewardAccount {
+            authority: Pubkey::default(),
+            reflection: reflection.add_rewards_account(weighted_amount, 0).unwrap(),
+            weighted_amount,
+            pools: 0,
+        }
+    }
+
+    fn claim(reflection: &mut ReflectionAccount, reward_account: &mut RewardAccount) -> u128 {
+        let amount = reflection.settle_rewards_account(reward_account).unwrap();
+        reward_account.reflection =
+            reflection.add_rewards_account(reward_account.weighted_amount, 0).unwrap();
+        amount
+    }
+
+    #[test]
+    fn close_pays_out_pending_rewards() {
+        let mut reflection = pool();
+        let mut alice = enter(&mut reflection, 3_000_000_000);
+        let bob = enter(&mut reflection, 1_000_000_000);
+
+        reflection.topup(400_000_000).unwrap();
+
+        let closed = reflection.settle_rewards_account(&bob).unwrap();
+        let claimed = claim(&mut reflection, &mut alice);
+
+        assert!(closed.abs_diff(100_000_000) <= 1);
+        assert!(claimed.abs_diff(300_000_000) <= 1);
+        assert!(closed + claimed <= 400_000_000);
+        assert!(400_000_000 - (closed + claimed) <= 2);
+    }
+
+    #[test]
+    fn close_does_not_lose_value_over_many_rounds() {
+        let mut reflection = pool();
+        let mut accounts: Vec<RewardAccount> = (1..=8)
+            .map(|i| enter(&mut reflection, i * 1_000_000_000))
+            .collect();
+
+        let mut distributed: u128 = 0;
+        let mut paid: u128 = 0;
+
+        for round in 1..=20u128 {
+            let amount = round * 7_000_003;
+            reflection.topup(amount).unwrap();
+            distributed += amount;
+
+            // one account closes and re-enters every round, the others keep accruing
+            let index = (round as usize) % accounts.len();
+            paid += reflection.settle_rewards_account(&accounts[index]).unwrap();
+            accounts[index] = enter(&mut reflection, accounts[index].weighted_amount);
+        }
+
+        for account in accounts.iter_mut() {
+            paid += claim(&mut reflection, account);
+        }
+
+        assert!(paid <= distributed);
+        assert!(distributed - paid <= 2 * 20 + 8);
+    }
+
+    #[test]
+    fn close_without_rewards_pays_nothing() {
+        let mut reflection = pool();
+        let alice = enter(&mut reflection, 1_000_000_000);
+
+        assert_eq!(reflection.settle_rewards_account(&alice).unwrap(), 0);
+        assert_eq!(reflection.total_reflection, 0);
+        assert_eq!(reflection.total_weighted_amount, 0);
+    }
+
+    /// A staker in the model, with its current stake weight and its accounts if entered.
+    struct Staker {
+        weighted_amount: u128,
+        accounts: Option<(RewardAccount, PoolRewardAccount)>,
+    }
+
+    #[derive(Clone, Debug)]
+    enum RewardOperation {
+        Enter { staker: prop::sample::Index },
+        Topup { amount: u64 },
+        PoolTopup { amount: u64 },
+        Emit { amount: u64 },
+        Wait { seconds: i64 },
+        Claim { staker: prop::sample::Index },
+        Sync { staker: prop::sample::Index },
+        Close { staker: prop::sample::Index },
+        // the stake changes without the reward accounts being synced
+        Restake { staker: prop::sample::Index, weighted_amount: u128 },
+    }
+
+    // the total weight stays below the supply the initial rate is derived from
+    const MAX_WEIGHT: u128 = 200_000_000_000_000;
+
+    fn reward_operation() -> impl Strategy<Value = RewardOperation> {
+        prop_oneof![
+            any::<prop::sample::Index>().prop_map(|staker| RewardOperation::Enter { staker }),
+            (0..=1_000_000_000_000u64).prop_map(|amount| RewardOperation::Topup { amount }),
+            (0..=1_000_000_000_000u64).prop_map(|amount| RewardOperation::PoolTopup { amount }),
+            (0..=1_000_000_000_000u64).prop_map(|amount| RewardOperation::Emit { amount }),
+            (0..=1_000_000i64).prop_map(|seconds| RewardOperation::Wait { seconds }),
+            any::<prop::sample::Index>().prop_map(|staker| RewardOperation::Claim { staker }),
+            any::<prop::sample::Index>().prop_map(|staker| RewardOperation::Sync { staker }),
+            any::<prop::sample::Index>().prop_map(|staker| RewardOperation::Close { staker }),
+            (any::<prop::sample::Index>(), 0..=MAX_WEIGHT).prop_map(|(staker, weighted_amount)| {
+                RewardOperation::Restake { staker, weighted_amount }
+            }),
+        ]
+    }
+
+    /// The reward vaults of the model, what was paid in minus what was paid out.
+    #[derive(Default)]
+    struct Vaults {
+        reflection: u128,
+        pool: u128,
+        reflection_dust: u128,
+    }
+
+    impl Vaults {
+        fn pay(vault: &mut u128, amount: u128) {
+            assert!(amount <= *vault, "{amount} paid from a vault of {vault}");
+            *vault -= amount;
+        }
+
+        /// Everything owed is covered, and one step rounds away at most a unit per account.
+        fn check(
+            &mut self,
+            reflection: &ReflectionAccount,
+            emission: &EmissionAccount,
+            pool: &PoolAccount,
+            stakers: &[Staker],
+        ) {
+            let (mut claimable, mut pool_claimable): (u128, u128) = (0, 0);
+            let entered = stakers.iter().filter_map(|staker| staker.accounts.as_ref());
+            for (reward_account, pool_account) in entered {
+                claimable += reflection.pending(reward_account).unwrap();
+                pool_claimable += pool.pending(pool_account).unwrap();
+            }
+            let owed: u128 = claimable + emission.remaining_amount as u128;
+
+            assert!(owed <= self.reflection, "{owed} owed, {} in vault", self.reflection);
+            assert!(pool_claimable <= self.pool, "{pool_claimable} owed, {} in pool", self.pool);
+
+            let dust: u128 = self.reflection - owed;
+            let rounded: u128 = dust.saturating_sub(self.reflection_dust);
+            assert!(rounded <= stakers.len() as u128 + 1, "{rounded} rounded away");
+            self.reflection_dust = dust;
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn random_reward_operations_keep_the_vault_solvent(
+            weights in prop::collection::vec(0..=MAX_WEIGHT, 1..16),
+            operations in prop::collection::vec(reward_operation(), 0..300),
+        ) {
+            let mut reflection = pool();
+            let mut pool = reward_pool();
+            let mut emission = emission(1_000_000, 1, 0);
+            let mut vaults = Vaults::default();
+            let mut now: i64 = 0;
+
+            let mut stakers: Vec<Staker> = weights
+                .into_iter()
+                .map(|weighted_amount| Staker { weighted_amount, accounts: None })
+                .collect();
+
+            for operation in operations {
+                // every instruction releases the emission first
+                emission.release(&mut reflection, now).unwrap();
+                vaults.check(&reflection, &emission, &pool, &stakers);
+
+                match operation {
+                    RewardOperation::Enter { staker } => {
+                        let staker = staker.get_mut(&mut stakers);
+                        if staker.accounts.is_none() {
+                            let mut reward_account = RewardAccount {
+                                authority: Pubkey::default(),
+                                reflection: 0,
+                                weighted_amount: 0,
+                                pools: 0,
+                            };
+                            reflection.enter(&mut reward_account, staker.weighted_amount).unwrap();
+                            let pool_reward_account = enter_pool(&mut pool, staker.weighted_amount);
+                            staker.accounts = Some((reward_account, pool_reward_account));
+                        }
+                    }
+                    // topups fail while there is no weight to distribute over
+                    RewardOperation::Topup { amount } => {
+                        if reflection.distribute(amount).is_ok() {
+                            vaults.reflection += amount as u128;
+                        }
+                    }
+                    RewardOperation::PoolTopup { amount } => {
+                        if pool.distribute(amount).is_ok() {
+                            vaults.pool += amount as u128;
+                        }
+                    }
+                    RewardOperation::Emit { amount } => {
+                        emission.extend(amount, now).unwrap();
+                        vaults.reflection += amount as u128;
+                    }
+                    RewardOperation::Wait { seconds } => now += seconds,
+                    RewardOperation::Claim { staker } => {
+                        let staker = staker.get_mut(&mut stakers);
+                        if let Some((reward_account, pool_account)) = staker.accounts.as_mut() {
+                            let weighted_amount: u128 = staker.weighted_amount;
+                            let amount = reflection.claim(reward_account, weighted_amount).unwrap();
+                            Vaults::pay(&mut vaults.reflection, amount);
+                            let amount = pool.claim(pool_account, weighted_amount).unwrap();
+                            Vaults::pay(&mut vaults.pool, amount);
+                        }
+                    }
+                    RewardOperation::Sync { staker } => {
+                        let staker = staker.get_mut(&mut stakers);
+                        if let Some((reward_account, pool_account)) = staker.accounts.as_mut() {
+                            reflection.sync(reward_account, staker.weighted_amount).unwrap();
+                            pool.sync(pool_account, staker.weighted_amount).unwrap();
+                        }
+                    }
+                    RewardOperation::Close { staker } => {
+                        let staker = staker.get_mut(&mut stakers);
+                        if let Some((reward_account, pool_account)) = staker.accounts.take() {
+                            let amount = reflection.settle(&reward_account).unwrap();
+                            Vaults::pay(&mut vaults.reflection, amount);
+                            Vaults::pay(&mut vaults.pool, pool.settle(&pool_account).unwrap());
+                        }
+                    }
+                    RewardOperation::Restake { staker, weighted_amount } => {
+                        staker.get_mut(&mut stakers).weighted_amount = weighted_amount;
+                    }
+                }
+
+                vaults.check(&reflection, &emission, &pool, &stakers);
+            }
+        }
+    }
+
+    fn reward_pool() -> PoolAccount {
+        PoolAccount {
+            stake_mint: Pubkey::default(),
+            reward_mint: Pubkey::default(),
+            reward_index: 0,
+            total_weighted_amount: 0,
+        }
+    }
+
+    fn enter_pool(pool: &mut PoolAccount, weighted_amount: u128) -> PoolRewardAccount {
+        let mut account = PoolRewardAccount {
+            authority: Pubkey::default(),
+            stake_account: Pubkey::default(),
+            pool: Pubkey::default(),
+            weighted_amount: 0,
+            reward_index: 0,
+            pending_amount: 0,
+        };
+        pool.enter(&mut account, weighted_amount).unwrap();
+        account
+    }
+
+    /// Both pool kinds split a topup by weight through the same [RewardPool] calls.
+    fn claims_split_a_topup_by_weight<P: RewardPool>(
+        pool: &mut P,
+        alice: &mut P::Account,
+        bob: &mut P::Account,
+    ) {
+        pool.distribute(400_000_000).unwrap();
+
+        let alice_amount = pool.claim(alice, 3_000_000_000).unwrap();
+        let bob_amount = pool.settle(bob).unwrap();
+
+        assert!(alice_amount.abs_diff(300_000_000) <= 1);
+        assert!(bob_amount.abs_diff(100_000_000) <= 1);
+        assert!(alice_amount + bob_amount <= 400_000_000);
+        assert_eq!(pool.pending(alice).unwrap(), 0);
+    }
+
+    #[test]
+    fn reflection_and_pools_share_the_claim_path() {
+        let mut reflection = pool();
+        let mut alice = enter(&mut reflection, 3_000_000_000);
+        let mut bob = enter(&mut reflection, 1_000_000_000);
+        claims_split_a_topup_by_weight(&mut reflection, &mut alice, &mut bob);
+
+        let mut pool = reward_pool();
+        let mut alice = enter_pool(&mut pool, 3_000_000_000);
+        let mut bob = enter_pool(&mut pool, 1_000_000_000);
+        claims_split_a_topup_by_weight(&mut pool, &mut alice, &mut bob);
+        assert_eq!(pool.total_weighted_amount, 3_000_000_000);
+    }
+
+    #[test]
+    fn pool_rewards_do_not_compound() {
+        let mut pool = reward_pool();
+        let mut alice = enter_pool(&mut pool, 1_000_000_000);
+        let bob = enter_pool(&mut pool, 1_000_000_000);
+
+        // alice claims in between, bob lets the rewards accrue, both keep earning the same
+        pool.distribute(500_000_000).unwrap();
+        assert_eq!(pool.claim(&mut alice, 1_000_000_000).unwrap(), 250_000_000);
+        pool.distribute(500_000_000).unwrap();
+
+        assert_eq!(pool.pending(&alice).unwrap(), 250_000_000);
+        assert_eq!(pool.pending(&bob).unwrap(), 500_000_000);
+        assert_eq!(pool.total_weighted_amount, 2_000_000_000);
+    }
+
+    #[test]
+    fn pool_sync_keeps_pending_rewards() {
+        let mut pool = reward_pool();
+        let mut alice = enter_pool(&mut pool, 1_000_000_000);
+        let bob = enter_pool(&mut pool, 1_000_000_000);
+
+        pool.distribute(200_000_000).unwrap();
+
+        // the stake of alice is closed, her accrued rewards stay claimable
+        pool.sync(&mut alice, 0).unwrap();
+        pool.distribute(200_000_000).unwrap();
+
+        assert_eq!(pool.pending(&alice).unwrap(), 100_000_000);
+        assert_eq!(pool.pending(&bob).unwrap(), 300_000_000);
+        assert_eq!(pool.settle(&alice).unwrap(), 100_000_000);
+        assert_eq!(pool.total_weighted_amount, 1_000_000_000);
+    }
+
+    #[test]
+    fn pool_without_weight_rejects_topups() {
+        let mut pool = reward_pool();
+
+        assert_eq!(
+            pool.distribute(1_000).unwrap_err(),
+            RewardErrors::ReflectionInvalid.into()
+        );
+
+        let alice = enter_pool(&mut pool, 1_000);
+        pool.distribute(1_000).unwrap();
+        assert_eq!(pool.settle(&alice).unwrap(), 1_000);
+        assert_eq!(
+            pool.distribute(1_000).unwrap_err(),
+            RewardErrors::ReflectionInvalid.into()
+        );
+    }
+
+    fn emission(rate: u64, end_time: i64, amount: u64) -> EmissionAccount {
+        let mut emission = EmissionAccount {
+            reflection: Pubkey::default(),
+            rate: 0,
+            end_time: 0,
+            last_update_time: 0,
+            remaining_amount: 0,
+        };
+        emission.configure(Pubkey::default(), rate, end_time, 0, amount).unwrap();
+        emission
+    }
+
+    #[test]
+    fn emission_kept_while_empty_is_released_after_the_end() {
+        let mut reflection = pool();
+        let mut emission = emission(10, 100, 1_000);
+
+        emission.release(&mut reflection, 150).unwrap();
+        assert_eq!(emission.remaining_amount, 1_000);
+
+        let mut alice = enter(&mut reflection, 1_000_000_000);
+        emission.release(&mut reflection, 200).unwrap();
+
+        assert_eq!(emission.remaining_amount, 0);
+        assert_eq!(claim(&mut reflection, &mut alice), 1_000);
+    }
+
+    #[test]
+    fn emission_extends_the_running_stream() {
+        let mut emission = emission(10, 100, 1_000);
+
+        assert_eq!(emission.accrue(40), 400);
+        emission.extend(505, 40).unwrap();
+
+        assert_eq!(emission.end_time, 151);
+        assert_eq!(emission.accrue(100), 600);
+        assert_eq!(emission.accrue(151), 505);
+        assert_eq!(emission.remaining_amount, 0);
+    }
+
+    #[test]
+    fn emission_restarts_after_the_end() {
+        let mut emission = emission(10, 100, 1_000);
+
+        assert_eq!(emission.accrue(300), 1_000);
+        emission.extend(200, 300).unwrap();
+
+        assert_eq!(emission.end_time, 320);
+        assert_eq!(emission.accrue(310), 100);
+        assert_eq!(emission.accrue(400), 100);
+    }
+
+    fn epoch_claim(start_epoch: u64) -> EpochClaimAccount {
+        let mut epoch_claim = EpochClaimAccount {
+            reward_account: Pubkey::default(),
+            start_epoch: 0,
+            claimed: [0; EPOCH_CLAIM_WORDS],
+        };
+        epoch_claim.reset(start_epoch);
+        epoch_claim
+    }
+
+    fn epoch(amount: u64, total_weighted_amount: u64) -> EpochAccount {
+        EpochAccount {
+            reflection: Pubkey::default(),
+            epoch: 0,
+            amount,
+            total_weighted_amount,
+            claimed_amount: 0,
+            swept_amount: 0,
+            open_time: 1_000,
+        }
+    }
+
+    const EPOCH_EXPIRY: i64 = 1_000 + (EPOCH_CLAIM_DAYS as u128 * SECONDS_PER_DAY) as i64;
+
+    #[test]
+    fn epochs_are_claimed_once_in_any_order() {
+        let mut epoch_claim = epoch_claim(10);
+
+        epoch_claim.claim(12).unwrap();
+        epoch_claim.claim(10).unwrap();
+
+        assert!(epoch_claim.is_claimed(10) && epoch_claim.is_claimed(12));
+        assert!(!epoch_claim.is_claimed(11));
+        assert_eq!(epoch_claim.claim(12).unwrap_err(), RewardErrors::EpochClaimed.into());
+        assert_eq!(epoch_claim.claim(9).unwrap_err(), RewardErrors::InvalidEpoch.into());
+
+        epoch_claim.claim(11).unwrap();
+        assert!(epoch_claim.is_claimed(11));
+    }
+
+    #[test]
+    fn epoch_window_slides_past_claimed_epochs() {
+        let mut epoch_claim = epoch_claim(0);
+        let window = 64 * EPOCH_CLAIM_WORDS as u64;
+
+        for epoch in 0..window {
+            epoch_claim.claim(epoch).unwrap();
+        }
+
+        assert_eq!(epoch_claim.start_epoch, window);
+        epoch_claim.claim(window).unwrap();
+        epoch_claim.claim(2 * window - 1).unwrap();
+        assert!(!epoch_claim.is_claimed(window + 1));
+    }
+
+    #[test]
+    fn epoch_window_drops_old_epochs_to_claim_new_ones() {
+        let mut epoch_claim = epoch_claim(0);
+        let window = 64 * EPOCH_CLAIM_WORDS as u64;
+
+        epoch_claim.claim(1).unwrap();
+        epoch_claim.claim(window + 64).unwrap();
+
+        // the two oldest words are dropped and count as claimed
+        assert_eq!(epoch_claim.start_epoch, 128);
+        assert!(epoch_claim.is_claimed(0) && epoch_claim.is_claimed(127));
+        assert!(epoch_claim.is_claimed(window + 64));
+        assert!(!epoch_claim.is_claimed(128));
+
+        epoch_claim.claim(10 * window).unwrap();
+        assert_eq!(epoch_claim.start_epoch, 9 * window + 64);
+        assert!(epoch_claim.is_claimed(10 * window));
+        assert!(!epoch_claim.is_claimed(10 * window - 1));
+    }
+
+    proptest! {
+        #[test]
+        fn epoch_shares_never_exceed_the_epoch(
+            amount in 0..=1_000_000_000_000u64,
+            weighted_amounts in prop::collection::vec(0..=200_000_000_000_000u64, 1..32),
+        ) {
+            let mut epoch = epoch(amount, weighted_amounts.iter().sum());
+
+            let paid: u64 = weighted_amounts
+                .iter()
+                .map(|weighted_amount| epoch.claim(*weighted_amount, EPOCH_EXPIRY - 1).unwrap())
+                .sum();
+            prop_assert_eq!(paid, epoch.claimed_amount);
+            prop_assert!(epoch.amount - paid <= weighted_amounts.len() as u64);
+        }
+    }
+
+    #[test]
+    fn epoch_claims_are_bounded_by_the_epoch() {
+        // a stake history out of step with the total can not take more than the epoch
+        let mut epoch = epoch(1_000, 100);
+
+        assert_eq!(epoch.claim(80, 1_000).unwrap(), 800);
+        assert_eq!(epoch.claim(80, 1_000).unwrap(), 200);
+        assert_eq!(epoch.claim(80, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn epoch_reserve_rejects_underflow_and_overflow() {
+        let mut config = RewardConfig {
+            authority: Pubkey::default(),
+            treasury: Pubkey::default(),
+            fee_basis_points: 0,
+            fee_cap: 0,
+            paused: false,
+            epoch_count: 0,
+            epoch_reserved_amount: 0,
+        };
+
+        config.open_epoch(1_000).unwrap();
+        config.release_epoch_amount(400).unwrap();
+        assert_eq!(
+            config.release_epoch_amount(601).unwrap_err(),
+            RewardErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            config.open_epoch(u64::MAX).unwrap_err(),
+            RewardErrors::MathOverflow.into()
+        );
+        assert_eq!((config.epoch_count, config.epoch_reserved_amount), (1, 600));
+    }
+
+    #[test]
+    fn expired_epochs_sweep_what_was_not_claimed() {
+        let mut epoch = epoch(1_000, 100);
+
+        assert_eq!(epoch.claim(30, 1_000).unwrap(), 300);
+        assert_eq!(
+            epoch.sweep(EPOCH_EXPIRY - 1).unwrap_err(),
+            RewardErrors::EpochNotExpired.into()
+        );
+        assert_eq!(epoch.sweep(EPOCH_EXPIRY).unwrap(), 700);
+        assert_eq!(epoch.claim(70, EPOCH_EXPIRY).unwrap_err(), RewardErrors::InvalidEpoch.into());
+        assert_eq!(epoch.sweep(EPOCH_EXPIRY).unwrap(), 0);
+        assert_eq!(epoch.claimed_amount + epoch.swept_amount, epoch.amount);
+    }
+
+    #[test]
+    fn checkpoints_are_read_at_the_snapshot() {
+        let checkpoint = |timestamp: i64, weighted_amount: u64| Checkpoint {
+            timestamp,
+            slot: 0,
+            amount: 0,
+            weighted_amount,
+        };
+        // a ring of three that wrapped, the oldest kept checkpoint is at 20
+        let checkpoints = [checkpoint(40, 4), checkpoint(20, 2), checkpoint(30, 3)];
+
+        assert_eq!(checkpoint_weighted_amount_at(4, &checkpoints, 19), None);
+        assert_eq!(checkpoint_weighted_amount_at(4, &checkpoints, 20), Some(2));
+        assert_eq!(checkpoint_weighted_amount_at(4, &checkpoints, 39), Some(3));
+        assert_eq!(checkpoint_weighted_amount_at(4, &checkpoints, 100), Some(4));
+        assert_eq!(checkpoint_weighted_amount_at(0, &checkpoints, 100), None);
+    }
 }
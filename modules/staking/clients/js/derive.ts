@@ -0,0 +1,106 @@
+import {
+  type Address,
+  getAddressEncoder,
+  getProgramDerivedAddress,
+  getU64Encoder,
+} from "@solana/kit";
+import { EFFECT_STAKING_PROGRAM_ADDRESS } from "./@generated/index.js";
+
+export const deriveStakeCounterPda = async ({
+  mint,
+  authority,
+}: {
+  mint: Address;
+  authority: Address;
+}) => {
+  const [stakeCounterAccount] = await getProgramDerivedAddress({
+    seeds: [
+      Buffer.from("counter", "utf-8"),
+      getAddressEncoder().encode(mint),
+      getAddressEncoder().encode(authority),
+    ],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  return {
+    stakeCounterAccount,
+  };
+};
+
+export const deriveStakeVaultPda = async ({
+  stakeAccount,
+}: {
+  stakeAccount: Address;
+}) => {
+  const [stakeVaultAccount] = await getProgramDerivedAddress({
+    seeds: [getAddressEncoder().encode(stakeAccount)],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  return {
+    stakeVaultAccount,
+  };
+};
+
+export const deriveStakeAccountsPda = async ({
+  mint,
+  authority,
+  index,
+}: {
+  mint: Address;
+  authority: Address;
+  index: number | bigint;
+}) => {
+  const [stakeAccount] = await getProgramDerivedAddress({
+    seeds: [
+      Buffer.from("stake", "utf-8"),
+      getAddressEncoder().encode(mint),
+      getAddressEncoder().encode(authority),
+      getU64Encoder().encode(index),
+    ],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  const { stakeVaultAccount } = await deriveStakeVaultPda({ stakeAccount });
+
+  return {
+    stakeAccount,
+    stakeVaultAccount,
+  };
+};
+
+export const deriveLiquidPoolPdas = async ({ mint }: { mint: Address }) => {
+  const [liquidAuthority] = await getProgramDerivedAddress({
+    seeds: [Buffer.from("liquid", "utf-8"), getAddressEncoder().encode(mint)],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  const [stakeAccount] = await getProgramDerivedAddress({
+    seeds: [
+      Buffer.from("stake", "utf-8"),
+      getAddressEncoder().encode(mint),
+      getAddressEncoder().encode(liquidAuthority),
+    ],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  const [receiptMint] = await getProgramDerivedAddress({
+    seeds: [Buffer.from("receipt", "utf-8"), getAddressEncoder().encode(mint)],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  const [reserveTokenAccount] = await getProgramDerivedAddress({
+    seeds: [Buffer.from("reserve", "utf-8"), getAddressEncoder().encode(mint)],
+    programAddress: EFFECT_STAKING_PROGRAM_ADDRESS,
+  });
+
+  const { stakeVaultAccount } = await deriveStakeVaultPda({ stakeAccount });
+
+  return {
+    liquidAuthority,
+    stakeAccount,
+    stakeVaultAccount,
+    receiptMint,
+    reserveTokenAccount,
+  };
+};
@@ -1,2 +1,3 @@
 export * from "./@generated/index.js";
+export * from "./derive.js";
 export * from "./fetch.js";
@@ -3,11 +3,23 @@ use effect_common::constants as common_constants;
 use anchor_lang::prelude::*;    
 
 #[constant]
-pub const STAKE_DURATION_MIN: u128 = common_constants::STAKE_DURATION_MIN; 
+pub const UNSTAKE_DELAY_DAYS: u64 = common_constants::UNSTAKE_DELAY_DAYS;
 #[constant]
-pub const STAKE_DURATION_MAX: u128 = common_constants::STAKE_DURATION_MAX;
+pub const DELEGATION_COOLDOWN_DAYS: u64 = common_constants::DELEGATION_COOLDOWN_DAYS;
 #[constant]
-pub const STAKE_MINIMUM_AMOUNT: u64 = common_constants::STAKE_MINIMUM_AMOUNT;
+pub const SLASH_MAX_BASIS_POINTS: u64 = common_constants::SLASH_MAX_BASIS_POINTS;
 #[constant]
-pub const UNSTAKE_DELAY_DAYS: u64 = common_constants::UNSTAKE_DELAY_DAYS;
+pub const SLASH_COOLDOWN_DAYS: u64 = common_constants::SLASH_COOLDOWN_DAYS;
+
+// lock tiers, a tier id indexes both the durations and the multipliers
+const DAY: u64 = common_constants::SECONDS_PER_DAY as u64;
+#[constant]
+pub const LOCK_TIER_DURATIONS: [u64; 4] = [30 * DAY, 90 * DAY, 180 * DAY, 365 * DAY];
+#[constant]
+pub const LOCK_TIER_MULTIPLIERS: [u64; 4] =
+    [10_000, 11_000, 12_500, common_constants::LOCK_TIER_MULTIPLIER_MAX]; // basis points
+
+// number of checkpoints kept in the ring buffers
+pub const STAKE_CHECKPOINTS: usize = 32;
+pub const TOTAL_CHECKPOINTS: usize = 256;
 
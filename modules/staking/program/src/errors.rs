@@ -37,4 +37,30 @@ pub enum StakingErrors {
     StakeNotEmpty,
     #[msg("Invalid Mint")]
     InvalidMint,
-}
\ No newline at end of file
+    #[msg("The unstake penalty is too high.")]
+    InvalidPenalty,
+    #[msg("No checkpoint found for this time.")]
+    CheckpointNotFound,
+    #[msg("This stake is already delegated.")]
+    AlreadyDelegated,
+    #[msg("This stake is not delegated.")]
+    NotDelegated,
+    #[msg("The delegation cooldown has not passed yet.")]
+    DelegationCooldown,
+    #[msg("The slash amount is too high.")]
+    SlashTooHigh,
+    #[msg("The slash cooldown has not passed yet.")]
+    SlashCooldown,
+    #[msg("Invalid slash destination.")]
+    InvalidSlashDestination,
+    #[msg("There are no rewards to compound.")]
+    NoRewards,
+    #[msg("Arithmetic overflow.")]
+    MathOverflow,
+    #[msg("This lock tier does not exist.")]
+    InvalidTier,
+    #[msg("Invalid operator account.")]
+    InvalidOperatorAccount,
+    #[msg("Invalid unstake account.")]
+    InvalidUnstakeAccount,
+}
@@ -0,0 +1,155 @@
+use anchor_lang::prelude::*;
+
+/***
+ * Events
+ */
+
+/// Emitted when a new [StakeAccount](#stake-account) is created.
+#[event]
+pub struct Staked {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub lock_duration: u64,
+    pub tier: u8,
+    pub stake_start_time: i64,
+    pub weighted_amount: u128,
+}
+
+/// Emitted when tokens are added to an existing stake, including genesis stakes.
+#[event]
+pub struct ToppedUp {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub stake_start_time: i64,
+    pub weighted_amount: u128,
+}
+
+/// Emitted when tokens leave a stake into an unstake vesting account.
+#[event]
+pub struct UnstakeStarted {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub stake_start_time: i64,
+    pub weighted_amount: u128,
+    pub vesting_account: Pubkey,
+    pub vesting_start_time: i64,
+    pub release_rate: u64,
+}
+
+/// Emitted when tokens leave a stake immediately against a penalty.
+#[event]
+pub struct UnstakedInstant {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub total_amount: u64,
+    pub stake_start_time: i64,
+    pub weighted_amount: u128,
+}
+
+/// Emitted when an unstake is cancelled and its tokens return to the stake.
+#[event]
+pub struct UnstakeCancelled {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub vesting_account: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub stake_start_time: i64,
+    pub weighted_amount: u128,
+}
+
+/// Emitted when a [StakeAccount](#stake-account) is delegated to an operator.
+#[event]
+pub struct Delegated {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub operator: Pubkey,
+    pub weight: u128,
+    pub operator_weight: u128,
+}
+
+/// Emitted when a [StakeAccount](#stake-account) is undelegated from an operator.
+#[event]
+pub struct Undelegated {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub operator: Pubkey,
+    pub weight: u128,
+    pub operator_weight: u128,
+}
+
+/// Emitted when the settings authority slashes a [StakeAccount](#stake-account).
+#[event]
+pub struct Slashed {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub destination_token_account: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub slashed_amount: u64,
+    pub weighted_amount: u128,
+}
+
+/// Emitted when the liquid stake pool is created.
+#[event]
+pub struct LiquidPoolCreated {
+    pub stake_account: Pubkey,
+    pub receipt_mint: Pubkey,
+}
+
+/// Emitted when tokens are staked into the liquid stake pool.
+#[event]
+pub struct LiquidStaked {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub receipts: u64,
+    pub total_amount: u64,
+    pub total_receipts: u64,
+}
+
+/// Emitted when receipts are redeemed from the liquid stake pool.
+#[event]
+pub struct LiquidRedeemed {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub penalty: u64,
+    pub receipts: u64,
+    pub total_amount: u64,
+    pub total_receipts: u64,
+}
+
+/// Emitted when rewards are compounded into the liquid stake pool.
+#[event]
+pub struct LiquidCompounded {
+    pub stake_account: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub total_receipts: u64,
+}
+
+/// Emitted when the rewards of a stake are compounded by the reward program.
+#[event]
+pub struct Compounded {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+    pub amount: u64,
+    pub total_amount: u64,
+    pub stake_start_time: i64,
+    pub weighted_amount: u128,
+}
+
+/// Emitted when a [StakeAccount](#stake-account) is closed.
+#[event]
+pub struct StakeClosed {
+    pub stake_account: Pubkey,
+    pub authority: Pubkey,
+}
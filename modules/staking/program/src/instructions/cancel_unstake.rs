@@ -0,0 +1,154 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+use effect_vesting::{accounts::VestingAccount, program::EffectVesting};
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CancelUnstake<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ StakingErrors::Unauthorized,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = stake_vault_token_account,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"unstake", vesting_account.key().as_ref()],
+        bump,
+        has_one = stake_account @ StakingErrors::InvalidVestingAccount,
+    )]
+    pub unstake_account: Account<'info, UnstakeAccount>,
+
+    #[account(mut)]
+    pub vesting_account: Account<'info, VestingAccount>,
+
+    #[account(
+        mut,
+        seeds = [vesting_account.key().as_ref()],
+        bump,
+        seeds::program = vesting_program.key(),
+    )]
+    pub vesting_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(address = crate::ID)]
+    pub staking_program: Program<'info, crate::program::EffectStaking>,
+
+    pub vesting_program: Program<'info, EffectVesting>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    pub reward_program: Option<Program<'info, EffectReward>>,
+
+    /// CHECK: synced when the stake entered the reward pool.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reward_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"reflection", stake_account.mint.as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reflection_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(mut)]
+    pub emission_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CancelUnstake<'info> {
+    pub fn handler(
+        &mut self,
+        restore_start_time: bool,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<UnstakeCancelled> {
+        let vault_amount: u64 = self.stake_vault_token_account.amount;
+
+        // pull the unvested tokens back and close the vesting account
+        cancel_vesting!(self, &[vault_seed!(self.stake_account.key())])?;
+
+        self.stake_vault_token_account.reload()?;
+        let amount: u64 = self.stake_vault_token_account.amount - vault_amount;
+        require!(amount > 0, StakingErrors::AmountNotEnough);
+
+        // restake, either at the age the stake had when it was unstaked or as a new topup
+        let now: i64 = Clock::get()?.unix_timestamp;
+        let new_time: i64 = if restore_start_time {
+            self.unstake_account.stake_start_time
+        } else {
+            now
+        };
+        self.stake_account.topup(amount, new_time, now)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+        sync_reward_if_entered!(
+            self,
+            self.reward_program.as_ref(),
+            self.reward_account.as_ref(),
+            pools
+        );
+
+        Ok(UnstakeCancelled {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            vesting_account: self.vesting_account.key(),
+            amount,
+            total_amount: self.stake_account.amount,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
+    }
+}
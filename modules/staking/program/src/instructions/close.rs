@@ -2,6 +2,9 @@ use crate::{id, *};
 use anchor_spl::token::{Token, TokenAccount};
 use effect_common::cpi;
 
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Close<'info> {
     #[account(mut)]
@@ -23,18 +26,139 @@ pub struct Close<'info> {
     ]
     pub stake_vault_token_account: Account<'info, TokenAccount>,
 
+    /// The reward account has to be closed first, so it is not left behind.
+    #[account(
+        constraint = reward_account.data_is_empty() @ StakingErrors::InvalidRewardAccount,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: SystemAccount<'info>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    pub reward_program: Program<'info, EffectReward>,
+
     pub token_program: Program<'info, Token>,
+
+    /// CHECK: the delegation of the stake, closed along with it.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is still delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the slashes of the stake, closed along with it.
+    #[account(
+        mut,
+        seeds = [b"slash", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub slash_account: UncheckedAccount<'info>,
 }
 
 impl<'info> Close<'info> {
-    pub fn handler(&self) -> Result<()> {
+    pub fn handler(&self, unstake_accounts: &[AccountInfo<'info>]) -> Result<StakeClosed> {
+        close_stake_records!(self, unstake_accounts);
+
         close_vault!(
             self,
             stake_vault_token_account,
             &[&vault_seed!(self.stake_account.key())]
-        )
+        )?;
+
+        Ok(StakeClosed {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+        })
+    }
+}
+
+/// Close an account kept next to a stake, if it was ever created.
+pub fn close_stake_record<'info>(
+    account: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    if account.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(*account.owner, crate::ID, StakingErrors::InvalidStakeAccount);
+    close_account(account, recipient)
+}
+
+/// Take the weight of a still delegated stake off its operator before the delegation is closed.
+pub fn release_delegation(
+    delegation_account: &AccountInfo,
+    operator_account: Option<&AccountInfo>,
+) -> Result<()> {
+    if delegation_account.data_is_empty() {
+        return Ok(());
     }
+    require_keys_eq!(
+        *delegation_account.owner,
+        crate::ID,
+        StakingErrors::NotDelegated
+    );
+
+    let delegation =
+        DelegationAccount::try_deserialize(&mut &delegation_account.try_borrow_data()?[..])?;
+    if !delegation.is_delegated() {
+        return Ok(());
+    }
+
+    let operator_account = operator_account.ok_or(StakingErrors::InvalidOperatorAccount)?;
+    require_keys_eq!(
+        *operator_account.owner,
+        crate::ID,
+        StakingErrors::InvalidOperatorAccount
+    );
+    let mut operator =
+        OperatorAccount::try_deserialize(&mut &operator_account.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        operator.operator,
+        delegation.operator,
+        StakingErrors::InvalidOperatorAccount
+    );
+
+    operator.remove(delegation.weight)?;
+    operator.try_serialize(&mut &mut operator_account.try_borrow_mut_data()?[..])
+}
+
+/// Close an unstake record of the stake, it can no longer be cancelled once the stake is gone.
+pub fn close_unstake_record<'info>(
+    stake_account: &Pubkey,
+    unstake_account: &AccountInfo<'info>,
+    recipient: &AccountInfo<'info>,
+) -> Result<()> {
+    require_keys_eq!(
+        *unstake_account.owner,
+        crate::ID,
+        StakingErrors::InvalidUnstakeAccount
+    );
+    let unstake = UnstakeAccount::try_deserialize(&mut &unstake_account.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        unstake.stake_account,
+        *stake_account,
+        StakingErrors::InvalidUnstakeAccount
+    );
+    close_account(unstake_account, recipient)
+}
+
+fn close_account<'info>(account: &AccountInfo<'info>, recipient: &AccountInfo<'info>) -> Result<()> {
+    let lamports: u64 = account.lamports();
+    **recipient.try_borrow_mut_lamports()? = recipient
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(StakingErrors::MathOverflow)?;
+    **account.try_borrow_mut_lamports()? = 0;
+
+    account.assign(&anchor_lang::system_program::ID);
+    account.resize(0)?;
+    Ok(())
 }
@@ -0,0 +1,95 @@
+use crate::{id, *};
+use anchor_spl::token::{Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseEmpty<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ StakingErrors::Unauthorized,
+        constraint = stake_account.amount == 0 @ StakingErrors::StakeNotEmpty,
+        constraint = Clock::get()?.unix_timestamp >= stake_account.lock_end()
+            @ StakingErrors::Locked,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        constraint = stake_vault_token_account.amount == 0 @ StakingErrors::VaultNotEmpty,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    /// The reward account has to be closed first, so it is not left behind.
+    #[account(
+        constraint = reward_account.data_is_empty() @ StakingErrors::InvalidRewardAccount,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: SystemAccount<'info>,
+
+    /// CHECK: receives the rent, validated against the stake authority.
+    #[account(mut)]
+    pub authority: UncheckedAccount<'info>,
+
+    pub reward_program: Program<'info, EffectReward>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: the delegation of the stake, closed along with it.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is still delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+
+    /// Tells a dust stake from a stake still waiting for its migration claim.
+    #[account(
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    /// CHECK: the slashes of the stake, closed along with it.
+    #[account(
+        mut,
+        seeds = [b"slash", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub slash_account: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseEmpty<'info> {
+    pub fn handler(&self, unstake_accounts: &[AccountInfo<'info>]) -> Result<StakeClosed> {
+        // a stake that never held tokens may still be claimed into, only its authority closes it
+        require!(
+            self.authority.is_signer
+                || self.stake_checkpoint_account.load()?.has_held_tokens(),
+            StakingErrors::Unauthorized
+        );
+
+        close_stake_records!(self, unstake_accounts);
+
+        close_vault!(
+            self,
+            stake_vault_token_account,
+            &[vault_seed!(self.stake_account.key())]
+        )?;
+
+        Ok(StakeClosed {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+        })
+    }
+}
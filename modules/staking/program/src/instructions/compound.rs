@@ -0,0 +1,93 @@
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+use effect_common::cpi;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    #[account(mut)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        token::mint = stake_account.mint,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: only used to derive the reward vault.
+    #[account(
+        seeds = [b"reflection", stake_account.mint.as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    /// CHECK: the reward vault signs through the reward program, paying out the compounded rewards.
+    #[account(
+        mut,
+        signer,
+        seeds = [reflection_account.key().as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reward_vault_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    pub token_program: Program<'info, Token>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> Compound<'info> {
+    pub fn handler(&mut self, amount: u64) -> Result<Compounded> {
+        require!(amount > 0, StakingErrors::NoRewards);
+
+        // compounding is a topup paid by the reward vault
+        let now = Clock::get()?.unix_timestamp;
+        self.stake_account.topup(amount, now, now)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+
+        transfer_tokens_from_vault!(
+            self,
+            reward_vault_token_account,
+            stake_vault_token_account,
+            &[],
+            amount
+        )?;
+
+        Ok(Compounded {
+            stake_account: self.stake_account.key(),
+            authority: self.stake_account.authority,
+            amount,
+            total_amount: self.stake_account.amount,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
+    }
+}
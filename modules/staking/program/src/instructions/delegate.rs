@@ -0,0 +1,64 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Delegate<'info> {
+    #[account(has_one = authority @ StakingErrors::Unauthorized)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = DelegationAccount::SIZE,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: Account<'info, DelegationAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = OperatorAccount::SIZE,
+        seeds = [b"operator", operator.key().as_ref()],
+        bump,
+    )]
+    pub operator_account: Account<'info, OperatorAccount>,
+
+    /// CHECK: the operator can be any account, it does not take custody of the stake
+    pub operator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Delegate<'info> {
+    pub fn handler(&mut self) -> Result<Delegated> {
+        let now = Clock::get()?.unix_timestamp;
+
+        // an existing delegation has to be removed first
+        require!(
+            !self.delegation_account.is_delegated(),
+            StakingErrors::AlreadyDelegated
+        );
+        self.delegation_account.check_cooldown(now)?;
+
+        let weight: u128 = self.stake_account.weighted_amount;
+        self.delegation_account.delegate(
+            self.stake_account.key(),
+            self.operator.key(),
+            weight,
+            now,
+        );
+        self.operator_account.add(self.operator.key(), weight)?;
+
+        Ok(Delegated {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            operator: self.operator.key(),
+            weight,
+            operator_weight: self.operator_account.delegated_weight,
+        })
+    }
+}
@@ -3,13 +3,14 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use effect_common::cpi::transfer_tokens;
 
 use effect_migration::{accounts::MigrationAccount, program::EffectMigration};
+use effect_reward::program::EffectReward;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct GenesisStake<'info> {
     #[account()]
     pub authority: Signer<'info>,
 
-    #[account()]
     pub mint: Account<'info, Mint>,
     
     #[account(
@@ -19,7 +20,10 @@ pub struct GenesisStake<'info> {
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
     pub stake_account: Account<'info, StakeAccount>,
     
     #[account(
@@ -52,6 +56,56 @@ pub struct GenesisStake<'info> {
     pub token_program: Program<'info, Token>,
 
     pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    pub reward_program: Option<Program<'info, EffectReward>>,
+
+    /// CHECK: synced when the stake entered the reward pool.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reward_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"reflection", stake_account.mint.as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reflection_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(mut)]
+    pub emission_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> GenesisStake<'info> {
@@ -59,10 +113,20 @@ impl<'info> GenesisStake<'info> {
         &mut self,
         amount: u64,
         stake_start_time: i64,
-    ) -> Result<()> {
+        pools: &[AccountInfo<'info>],
+    ) -> Result<ToppedUp> {
 
         // We always do a topup here, as to only allow already initialized stakes.
-        self.stake_account.topup(amount, stake_start_time);
+        self.stake_account
+            .topup(amount, stake_start_time, Clock::get()?.unix_timestamp)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+        sync_reward_if_entered!(
+            self,
+            self.reward_program.as_ref(),
+            self.reward_account.as_ref(),
+            pools
+        );
       
         // Transfer tokens from claim vault to the stake vault
         transfer_tokens(
@@ -72,6 +136,15 @@ impl<'info> GenesisStake<'info> {
             self.migration_vault_token_account.to_account_info(),
             &[],
             amount,
-        )
+        )?;
+
+        Ok(ToppedUp {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            total_amount: self.stake_account.amount,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
     }
 }
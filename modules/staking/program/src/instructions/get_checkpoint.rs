@@ -0,0 +1,28 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct GetCheckpoint<'info> {
+    #[account(
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    pub stake_checkpoint_account: Option<AccountLoader<'info, StakeCheckpointAccount>>,
+}
+
+impl<'info> GetCheckpoint<'info> {
+    pub fn handler(&self, timestamp: i64) -> Result<u64> {
+        // the stake amount if a stake is given, the total amount otherwise
+        let checkpoint: Option<Checkpoint> = match &self.stake_checkpoint_account {
+            Some(stake_checkpoint_account) => stake_checkpoint_account
+                .load()?
+                .get_checkpoint_at(timestamp),
+            None => self.total_checkpoint_account.load()?.get_checkpoint_at(timestamp),
+        };
+
+        checkpoint
+            .map(|checkpoint| checkpoint.amount)
+            .ok_or(StakingErrors::CheckpointNotFound.into())
+    }
+}
@@ -0,0 +1,51 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct GetVotingPower<'info> {
+    /// CHECK: the wallet whose stakes are aggregated, it does not need to sign.
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+}
+
+impl<'info> GetVotingPower<'info> {
+    pub fn handler(&self, stake_accounts: &[AccountInfo<'info>]) -> Result<u128> {
+        require!(
+            !stake_accounts.is_empty(),
+            StakingErrors::InvalidStakeAccount
+        );
+
+        let now: i64 = Clock::get()?.unix_timestamp;
+        let mut counted: Vec<Pubkey> = Vec::with_capacity(stake_accounts.len());
+        let mut voting_power: u128 = 0;
+
+        for info in stake_accounts {
+            // every stake may only be counted once
+            require!(
+                info.owner == &crate::ID && !counted.contains(info.key),
+                StakingErrors::InvalidStakeAccount
+            );
+            counted.push(info.key());
+
+            let stake_account = StakeAccount::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+            require_keys_eq!(
+                stake_account.authority,
+                self.authority.key(),
+                StakingErrors::Unauthorized
+            );
+            require_keys_eq!(
+                stake_account.mint,
+                self.settings_account.mint,
+                StakingErrors::InvalidMint
+            );
+
+            voting_power += stake_account.voting_power(now);
+        }
+
+        Ok(voting_power)
+    }
+}
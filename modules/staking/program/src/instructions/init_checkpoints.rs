@@ -0,0 +1,33 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct InitCheckpoints<'info> {
+    #[account(has_one = authority @ StakingErrors::Unauthorized)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = StakeCheckpointAccount::SIZE,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitCheckpoints<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        // history starts with the current amount
+        self.stake_checkpoint_account.load_init()?.record(
+            self.stake_account.amount,
+            self.stake_account.checkpoint_weighted_amount()?,
+            &Clock::get()?,
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use crate::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::id::ADMIN_AUTHORITY;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitLiquidPool<'info> {
+    #[account(address = settings_account.mint @ StakingErrors::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    /// The authority of the pooled stake, it signs for the pool and pays its reward account.
+    #[account(
+        mut,
+        seeds = [b"liquid", mint.key().as_ref()],
+        bump,
+    )]
+    pub liquid_authority: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<StakeAccount>(),
+        seeds = [b"stake", mint.key().as_ref(), liquid_authority.key().as_ref()],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = StakeCheckpointAccount::SIZE,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = stake_vault_token_account,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = mint.decimals,
+        mint::authority = liquid_authority,
+        seeds = [b"receipt", mint.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// Receives the claimed rewards of the pool before they are compounded.
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = liquid_authority,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: created by the reward program.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"emission", reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    #[account(mut, address = ADMIN_AUTHORITY @ StakingErrors::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub reward_program: Program<'info, EffectReward>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+}
+
+impl<'info> InitLiquidPool<'info> {
+    pub fn handler(&mut self) -> Result<LiquidPoolCreated> {
+        // the pool starts empty in the shortest lock tier
+        let now: i64 = Clock::get()?.unix_timestamp;
+        self.stake_account.init(
+            0,
+            self.liquid_authority.key(),
+            0,
+            now,
+            self.mint.key(),
+            now,
+        )?;
+        self.stake_checkpoint_account
+            .load_init()?
+            .record(0, 0, &Clock::get()?);
+
+        // fund the pool authority with the rent of its reward account, sized like the reward program
+        let reward_account_space: usize =
+            8 + 8 + std::mem::size_of::<effect_reward::accounts::RewardAccount>();
+        transfer(
+            CpiContext::new(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.authority.to_account_info(),
+                    to: self.liquid_authority.to_account_info(),
+                },
+            ),
+            Rent::get()?.minimum_balance(reward_account_space),
+        )?;
+
+        // the reward program reads the stake, so it is persisted before entering the pool
+        self.stake_account.exit(&crate::ID)?;
+        effect_reward::cpi::enter(CpiContext::new_with_signer(
+            self.reward_program.to_account_info(),
+            effect_reward::cpi::accounts::Enter {
+                reflection_account: self.reflection_account.to_account_info(),
+                emission_account: self.emission_account.to_account_info(),
+                stake_account: self.stake_account.to_account_info(),
+                stake_vault_token_account: self.stake_vault_token_account.to_account_info(),
+                reward_account: self.reward_account.to_account_info(),
+                authority: self.liquid_authority.to_account_info(),
+                mint: self.mint.to_account_info(),
+                stake_program: self.program.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+            },
+            &[liquid_seed!(self.mint.key())],
+        ))?;
+
+        Ok(LiquidPoolCreated {
+            stake_account: self.stake_account.key(),
+            receipt_mint: self.receipt_mint.key(),
+        })
+    }
+}
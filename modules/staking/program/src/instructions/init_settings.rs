@@ -0,0 +1,41 @@
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+use effect_common::id::ADMIN_AUTHORITY;
+
+#[derive(Accounts)]
+pub struct InitSettings<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = SettingsAccount::SIZE,
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    pub mint: Account<'info, Mint>,
+
+    #[account(token::mint = mint)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: any account can become the slashing authority.
+    pub slash_authority: UncheckedAccount<'info>,
+
+    #[account(mut, address = ADMIN_AUTHORITY @ StakingErrors::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitSettings<'info> {
+    pub fn handler(&mut self, unstake_penalty: u16, minimum_stake_amount: u64) -> Result<()> {
+        self.settings_account.mint = self.mint.key();
+        self.settings_account.set(
+            self.authority.key(),
+            self.slash_authority.key(),
+            self.token_account.key(),
+            unstake_penalty,
+            minimum_stake_amount,
+        )
+    }
+}
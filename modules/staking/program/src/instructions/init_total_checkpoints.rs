@@ -0,0 +1,29 @@
+use crate::*;
+use effect_common::id::ADMIN_AUTHORITY;
+
+#[derive(Accounts)]
+pub struct InitTotalCheckpoints<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = TotalCheckpointAccount::SIZE,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(mut, address = ADMIN_AUTHORITY @ StakingErrors::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTotalCheckpoints<'info> {
+    pub fn handler(&mut self, amount: u64, weighted_amount: u64) -> Result<()> {
+        // history starts with the amount staked before checkpoints existed
+        self.total_checkpoint_account
+            .load_init()?
+            .record(amount, weighted_amount, &Clock::get()?);
+        Ok(())
+    }
+}
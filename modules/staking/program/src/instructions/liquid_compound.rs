@@ -0,0 +1,119 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidCompound<'info> {
+    pub mint: Account<'info, Mint>,
+
+    /// CHECK: the authority of the pooled stake, signs for claiming its rewards.
+    #[account(
+        mut,
+        seeds = [b"liquid", mint.key().as_ref()],
+        bump,
+    )]
+    pub liquid_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", mint.key().as_ref(), liquid_authority.key().as_ref()],
+        bump,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        seeds = [b"receipt", mint.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// CHECK: validated by the reward program.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"emission", reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program.
+    #[account(
+        mut,
+        seeds = [reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_vault_token_account: UncheckedAccount<'info>,
+
+    pub reward_program: Program<'info, EffectReward>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+}
+
+impl<'info> LiquidCompound<'info> {
+    pub fn handler(&mut self, pools: &[AccountInfo<'info>]) -> Result<LiquidCompounded> {
+        // claim the rewards of the pool into its reserve and stake them
+        let amount: u64 = compound_liquid_rewards!(self);
+        require!(amount > 0, StakingErrors::NoRewards);
+
+        // the reward program reads the stake, so it is persisted before the reflection is synced
+        self.stake_account.exit(&crate::ID)?;
+        sync_reward!(self, pools)?;
+
+        Ok(LiquidCompounded {
+            stake_account: self.stake_account.key(),
+            amount,
+            total_amount: self.stake_account.amount,
+            total_receipts: self.receipt_mint.supply,
+        })
+    }
+}
@@ -0,0 +1,204 @@
+use crate::*;
+use anchor_spl::token::{burn, Burn, Mint, Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidRedeem<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+        token::authority = authority,
+    )]
+    pub receipt_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: the authority of the pooled stake, signs for the pool.
+    #[account(
+        mut,
+        seeds = [b"liquid", mint.key().as_ref()],
+        bump,
+    )]
+    pub liquid_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", mint.key().as_ref(), liquid_authority.key().as_ref()],
+        bump,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", mint.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"emission", reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when claiming the pool rewards.
+    #[account(
+        mut,
+        seeds = [reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_vault_token_account: UncheckedAccount<'info>,
+
+    /// Receives the claimed rewards of the pool before they are compounded.
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [reward_vault_token_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+        token::mint = mint,
+    )]
+    pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub reward_program: Program<'info, EffectReward>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+}
+
+impl<'info> LiquidRedeem<'info> {
+    pub fn handler(
+        &mut self,
+        receipts: u64,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<LiquidRedeemed> {
+        require!(
+            receipts > 0 && receipts <= self.receipt_mint.supply,
+            StakingErrors::AmountNotEnough
+        );
+
+        // pending rewards belong to the current holders, so they are staked before pricing
+        compound_liquid_rewards!(self);
+
+        // the receipts are worth their share of the pooled stake
+        let amount: u64 = (receipts as u128 * self.stake_account.amount as u128
+            / self.receipt_mint.supply as u128) as u64;
+        require!(amount > 0, StakingErrors::AmountNotEnough);
+
+        burn(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                Burn {
+                    mint: self.receipt_mint.to_account_info(),
+                    from: self.receipt_token_account.to_account_info(),
+                    authority: self.authority.to_account_info(),
+                },
+            ),
+            receipts,
+        )?;
+
+        // redeeming skips the unstake vesting and the pool lock never runs out, so the penalty
+        // is charged as if the full lock of the pool remained
+        let penalty: u64 = self
+            .settings_account
+            .get_redeem_penalty(amount, &self.stake_account);
+
+        transfer_tokens_from_vault!(
+            self,
+            stake_vault_token_account,
+            intermediate_reward_vault_token_account,
+            &[vault_seed!(self.stake_account.key())],
+            penalty
+        )?;
+
+        transfer_tokens_from_vault!(
+            self,
+            stake_vault_token_account,
+            recipient_token_account,
+            &[vault_seed!(self.stake_account.key())],
+            amount - penalty
+        )?;
+
+        self.stake_account.unstake(amount)?;
+        record_checkpoints!(self);
+
+        // the reward program reads the stake, so it is persisted before the reflection is synced
+        self.stake_account.exit(&crate::ID)?;
+        sync_reward!(self, pools)?;
+
+        Ok(LiquidRedeemed {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            penalty,
+            receipts,
+            total_amount: self.stake_account.amount,
+            total_receipts: self.receipt_mint.supply - receipts,
+        })
+    }
+}
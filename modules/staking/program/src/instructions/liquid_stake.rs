@@ -0,0 +1,166 @@
+use crate::*;
+use anchor_spl::token::{mint_to, Mint, MintTo, Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct LiquidStake<'info> {
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = receipt_mint,
+    )]
+    pub receipt_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: the authority of the pooled stake, signs for the pool.
+    #[account(
+        mut,
+        seeds = [b"liquid", mint.key().as_ref()],
+        bump,
+    )]
+    pub liquid_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"stake", mint.key().as_ref(), liquid_authority.key().as_ref()],
+        bump,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"receipt", mint.key().as_ref()],
+        bump,
+    )]
+    pub receipt_mint: Account<'info, Mint>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"emission", reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when claiming the pool rewards.
+    #[account(
+        mut,
+        seeds = [reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_vault_token_account: UncheckedAccount<'info>,
+
+    /// Receives the claimed rewards of the pool before they are compounded.
+    #[account(
+        mut,
+        seeds = [b"reserve", mint.key().as_ref()],
+        bump,
+    )]
+    pub reserve_token_account: Account<'info, TokenAccount>,
+
+    pub authority: Signer<'info>,
+
+    pub reward_program: Program<'info, EffectReward>,
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+}
+
+impl<'info> LiquidStake<'info> {
+    pub fn handler(
+        &mut self,
+        amount: u64,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<LiquidStaked> {
+        require!(amount > 0, StakingErrors::AmountNotEnough);
+
+        // pending rewards belong to the current holders, so they are staked before pricing
+        compound_liquid_rewards!(self);
+
+        // receipts are issued pro-rata to the pooled stake
+        let receipts: u64 = if self.receipt_mint.supply == 0 || self.stake_account.amount == 0 {
+            amount
+        } else {
+            (amount as u128 * self.receipt_mint.supply as u128 / self.stake_account.amount as u128)
+                as u64
+        };
+        require!(receipts > 0, StakingErrors::AmountNotEnough);
+
+        transfer_tokens_to_vault!(self, stake_vault_token_account, amount)?;
+        let now: i64 = Clock::get()?.unix_timestamp;
+        self.stake_account.topup(amount, now, now)?;
+        record_checkpoints!(self);
+
+        mint_to(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                MintTo {
+                    mint: self.receipt_mint.to_account_info(),
+                    to: self.receipt_token_account.to_account_info(),
+                    authority: self.liquid_authority.to_account_info(),
+                },
+                &[liquid_seed!(self.mint.key())],
+            ),
+            receipts,
+        )?;
+
+        // the reward program reads the stake, so it is persisted before the reflection is synced
+        self.stake_account.exit(&crate::ID)?;
+        sync_reward!(self, pools)?;
+
+        Ok(LiquidStaked {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            receipts,
+            total_amount: self.stake_account.amount,
+            total_receipts: self.receipt_mint.supply + receipts,
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use crate::*;
+use anchor_spl::token::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+pub struct MigrateMint<'info> {
+    #[account(
+        mut,
+        constraint = stake_account.mint == Pubkey::default() @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        token::mint = mint,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = settings_account.mint @ StakingErrors::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+}
+
+impl<'info> MigrateMint<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        // legacy stakes never had their mint recorded, take it from the vault
+        self.stake_account.mint = self.mint.key();
+        Ok(())
+    }
+}
@@ -1,13 +1,53 @@
 //! Instructions for Effect Staking.
 
+pub mod cancel_unstake;
 pub mod close;
+pub mod close_empty;
+pub mod compound;
+pub mod delegate;
+pub mod slash;
 pub mod stake;
+pub mod stake_pda;
+pub mod sync_delegation;
 pub mod topup;
+pub mod undelegate;
 pub mod unstake;
+pub mod unstake_instant;
+pub mod update_settings;
 pub mod genesis_stake;
+pub mod get_checkpoint;
+pub mod get_voting_power;
+pub mod init_checkpoints;
+pub mod init_liquid_pool;
+pub mod init_settings;
+pub mod init_total_checkpoints;
+pub mod liquid_compound;
+pub mod liquid_redeem;
+pub mod liquid_stake;
+pub mod migrate_mint;
 
+pub use cancel_unstake::*;
 pub use close::*;
+pub use close_empty::*;
+pub use compound::*;
+pub use delegate::*;
+pub use slash::*;
 pub use stake::*;
+pub use stake_pda::*;
+pub use sync_delegation::*;
 pub use topup::*;
+pub use undelegate::*;
 pub use unstake::*;
+pub use unstake_instant::*;
+pub use update_settings::*;
 pub use genesis_stake::*;
+pub use get_checkpoint::*;
+pub use get_voting_power::*;
+pub use init_checkpoints::*;
+pub use init_liquid_pool::*;
+pub use init_settings::*;
+pub use init_total_checkpoints::*;
+pub use liquid_compound::*;
+pub use liquid_redeem::*;
+pub use liquid_stake::*;
+pub use migrate_mint::*;
@@ -0,0 +1,179 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Slash<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+        constraint = settings_account.slash_authority == authority.key()
+            @ StakingErrors::Unauthorized,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    #[account(
+        mut,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = stake_vault_token_account,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = SlashAccount::SIZE,
+        seeds = [b"slash", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub slash_account: Account<'info, SlashAccount>,
+
+    /// CHECK: synced when the stake takes part in the reward pool.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"emission", reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub emission_account: UncheckedAccount<'info>,
+
+    /// CHECK: only used to derive the intermediate reward vault.
+    #[account(
+        seeds = [reflection_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_vault_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: only used to validate the destination.
+    #[account(
+        seeds = [reward_vault_token_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub intermediate_reward_vault_token_account: UncheckedAccount<'info>,
+
+    /// The settings token account, or the intermediate reward vault to redistribute the slash.
+    #[account(
+        mut,
+        token::mint = mint,
+        constraint = destination_token_account.key() == settings_account.token_account
+            || destination_token_account.key() == intermediate_reward_vault_token_account.key()
+            @ StakingErrors::InvalidSlashDestination,
+    )]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = settings_account.mint @ StakingErrors::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    pub reward_program: Program<'info, EffectReward>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> Slash<'info> {
+    pub fn handler(
+        &mut self,
+        amount: u64,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<Slashed> {
+        require!(amount > 0, StakingErrors::AmountNotEnough);
+
+        // check the cap and cooldown, and record the slash
+        self.slash_account.slash(
+            self.stake_account.key(),
+            amount,
+            self.stake_account.amount,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        transfer_tokens_from_vault!(
+            self,
+            stake_vault_token_account,
+            destination_token_account,
+            &[vault_seed!(self.stake_account.key())],
+            amount
+        )?;
+
+        // deduct the amount from the stake account
+        self.stake_account.unstake(amount)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+
+        // the reward program reads the stake, so it is persisted before the reflection is synced
+        if !self.reward_account.data_is_empty() {
+            self.stake_account.exit(&crate::ID)?;
+            sync_reward!(self, pools)?;
+        }
+
+        Ok(Slashed {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            destination_token_account: self.destination_token_account.key(),
+            amount,
+            total_amount: self.stake_account.amount,
+            slashed_amount: self.slash_account.slashed_amount,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
+    }
+}
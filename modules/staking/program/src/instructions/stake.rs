@@ -1,11 +1,13 @@
 use crate::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
-use constants::{STAKE_DURATION_MAX, STAKE_DURATION_MIN};
-use effect_common::{constants::STAKE_MINIMUM_AMOUNT, cpi};
+use effect_common::cpi;
 
+use effect_migration::accounts::MigrationAccount;
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Stake<'info> {
-    #[account(mut)]
+    #[account(mut, address = settings_account.mint @ StakingErrors::InvalidMint)]
     pub mint: Account<'info, Mint>,
 
     #[account(mut)]
@@ -34,32 +36,76 @@ pub struct Stake<'info> {
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
     pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    /// A stake opened again at the address of a closed stake continues its history.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = StakeCheckpointAccount::SIZE,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    /// An empty stake can only be opened for a pending migration claim of the mint.
+    #[account(
+        seeds = [mint.key().as_ref(), migration_account.foreign_address.as_slice()],
+        bump,
+        seeds::program = effect_migration::ID,
+    )]
+    pub migration_account: Option<Account<'info, MigrationAccount>>,
 }
 
 impl<'info> Stake<'info> {
-    pub fn handler(&mut self, amount: u64, duration: u128) -> Result<()> {
-        require!(
-            duration >= STAKE_DURATION_MIN,
-            StakingErrors::DurationTooShort
-        );
-        require!(
-            duration <= STAKE_DURATION_MAX,
-            StakingErrors::DurationTooLong
-        );
-        require!(
-            amount >= STAKE_MINIMUM_AMOUNT,
-            StakingErrors::AmountNotEnough
-        );
+    pub fn handler(&mut self, amount: u64, tier: u8) -> Result<Staked> {
+        check_stake(amount, self.migration_account.is_some())?;
+        self.settings_account.check_stake_amount(amount)?;
 
         // get stake account and init stake
+        let clock = Clock::get()?;
         self.stake_account.init(
             amount,
             self.authority.key(),
-            duration.try_into().unwrap(),
-            Clock::get().unwrap().unix_timestamp,
-        );
+            tier,
+            clock.unix_timestamp,
+            self.mint.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // keep track of the staked amounts
+        record_checkpoints!(self);
 
         // transfer tokens to the vault
-        transfer_tokens_to_vault!(self, stake_vault_token_account, amount)
+        transfer_tokens_to_vault!(self, stake_vault_token_account, amount)?;
+
+        Ok(Staked {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            lock_duration: self.stake_account.lock_duration,
+            tier,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
     }
 }
+
+/// Validate the amount of a new stake, only a stake opened for a migration claim may start empty.
+/// The lock duration follows from the tier, which is validated when the stake is initialized.
+pub fn check_stake(amount: u64, migration: bool) -> Result<()> {
+    require!(amount > 0 || migration, StakingErrors::AmountNotEnough);
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_migration::accounts::MigrationAccount;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct StakePda<'info> {
+    #[account(address = settings_account.mint @ StakingErrors::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    #[account(mut)]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = StakeCounterAccount::SIZE,
+        seeds = [b"counter", mint.key().as_ref(), authority.key().as_ref()],
+        bump,
+    )]
+    pub stake_counter_account: Account<'info, StakeCounterAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<StakeAccount>(),
+        seeds = [
+            b"stake",
+            mint.key().as_ref(),
+            authority.key().as_ref(),
+            &stake_counter_account.count.to_le_bytes(),
+        ],
+        bump,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = stake_vault_token_account,
+        seeds = [ stake_account.key().as_ref() ],
+        bump,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    /// A stake opened again at the address of a closed stake continues its history.
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = StakeCheckpointAccount::SIZE,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    /// An empty stake can only be opened for a pending migration claim of the mint.
+    #[account(
+        seeds = [mint.key().as_ref(), migration_account.foreign_address.as_slice()],
+        bump,
+        seeds::program = effect_migration::ID,
+    )]
+    pub migration_account: Option<Account<'info, MigrationAccount>>,
+}
+
+impl<'info> StakePda<'info> {
+    pub fn handler(&mut self, amount: u64, tier: u8) -> Result<Staked> {
+        check_stake(amount, self.migration_account.is_some())?;
+        self.settings_account.check_stake_amount(amount)?;
+
+        // get stake account and init stake
+        let clock = Clock::get()?;
+        self.stake_account.init(
+            amount,
+            self.authority.key(),
+            tier,
+            clock.unix_timestamp,
+            self.mint.key(),
+            clock.unix_timestamp,
+        )?;
+
+        // the next stake of this authority gets the next index
+        self.stake_counter_account.increment()?;
+
+        // keep track of the staked amounts
+        record_checkpoints!(self);
+
+        // transfer tokens to the vault
+        transfer_tokens_to_vault!(self, stake_vault_token_account, amount)?;
+
+        Ok(Staked {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            lock_duration: self.stake_account.lock_duration,
+            tier,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
+    }
+}
@@ -0,0 +1,84 @@
+use crate::*;
+
+#[derive(Accounts)]
+pub struct SyncDelegation<'info> {
+    /// CHECK: the stake of the delegation, it might be closed since
+    #[account(address = delegation_account.stake_account @ StakingErrors::InvalidStakeAccount)]
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub delegation_account: Account<'info, DelegationAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"operator", delegation_account.operator.as_ref()],
+        bump,
+    )]
+    pub operator_account: Account<'info, OperatorAccount>,
+}
+
+impl<'info> SyncDelegation<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        require!(
+            self.delegation_account.is_delegated(),
+            StakingErrors::NotDelegated
+        );
+
+        // a closed stake has no weight left
+        let weight: u128 = if self.stake_account.owner == &crate::ID {
+            let data = self.stake_account.try_borrow_data()?;
+            StakeAccount::try_deserialize(&mut &data[..])?.weighted_amount
+        } else {
+            0
+        };
+
+        self.operator_account
+            .sync(self.delegation_account.weight, weight)?;
+        self.delegation_account.weight = weight;
+        Ok(())
+    }
+}
+
+/// Move the delegated weight of a stake to its new `weight`, stakes that are not delegated are
+/// left untouched.
+pub fn sync_delegated_weight(
+    delegation_account: &AccountInfo,
+    operator_account: Option<&AccountInfo>,
+    weight: u128,
+) -> Result<()> {
+    if delegation_account.data_is_empty() {
+        return Ok(());
+    }
+    require_keys_eq!(
+        *delegation_account.owner,
+        crate::ID,
+        StakingErrors::NotDelegated
+    );
+
+    let mut delegation =
+        DelegationAccount::try_deserialize(&mut &delegation_account.try_borrow_data()?[..])?;
+    if !delegation.is_delegated() || delegation.weight == weight {
+        return Ok(());
+    }
+
+    // the operator has to be given along with a delegated stake
+    let operator_account = operator_account.ok_or(StakingErrors::InvalidOperatorAccount)?;
+    require_keys_eq!(
+        *operator_account.owner,
+        crate::ID,
+        StakingErrors::InvalidOperatorAccount
+    );
+    let mut operator =
+        OperatorAccount::try_deserialize(&mut &operator_account.try_borrow_data()?[..])?;
+    require_keys_eq!(
+        operator.operator,
+        delegation.operator,
+        StakingErrors::InvalidOperatorAccount
+    );
+
+    operator.sync(delegation.weight, weight)?;
+    delegation.weight = weight;
+
+    operator.try_serialize(&mut &mut operator_account.try_borrow_mut_data()?[..])?;
+    delegation.try_serialize(&mut &mut delegation_account.try_borrow_mut_data()?[..])
+}
@@ -1,7 +1,9 @@
 use crate::*;
 use anchor_spl::token::{Token, TokenAccount};
 use effect_common::cpi;
+use effect_reward::program::EffectReward;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Topup<'info> {
     #[account(mut)]
@@ -10,6 +12,7 @@ pub struct Topup<'info> {
     #[account(
         mut,
         has_one = authority @ StakingErrors::Unauthorized,
+        constraint = stake_account.mint == settings_account.mint @ StakingErrors::InvalidMint,
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
@@ -17,24 +20,104 @@ pub struct Topup<'info> {
         mut,
         seeds = [stake_account.key().as_ref()],
         bump,
+        token::mint = stake_account.mint,
     )]
     pub stake_vault_token_account: Account<'info, TokenAccount>,
 
     pub authority: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    pub reward_program: Option<Program<'info, EffectReward>>,
+
+    /// CHECK: synced when the stake entered the reward pool.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reward_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(
+        mut,
+        seeds = [b"reflection", stake_account.mint.as_ref()],
+        bump,
+        seeds::program = effect_reward::ID,
+    )]
+    pub reflection_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(mut)]
+    pub emission_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> Topup<'info> {
-    pub fn handler(&mut self, amount: u64) -> Result<()> {
+    pub fn handler(
+        &mut self,
+        amount: u64,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<ToppedUp> {
         // test amount
         require!(amount > 0, StakingErrors::AmountNotEnough);
 
         // get stake account and topup stake
         let new_time = Clock::get().unwrap().unix_timestamp;
-        self.stake_account.topup(amount, new_time);
+        self.stake_account.topup(amount, new_time, new_time)?;
+        self.settings_account
+            .check_stake_amount(self.stake_account.amount)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+        sync_reward_if_entered!(
+            self,
+            self.reward_program.as_ref(),
+            self.reward_account.as_ref(),
+            pools
+        );
 
         // transfer tokens to the vault
-        transfer_tokens_to_vault!(self, stake_vault_token_account, amount)
+        transfer_tokens_to_vault!(self, stake_vault_token_account, amount)?;
+
+        Ok(ToppedUp {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            total_amount: self.stake_account.amount,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
     }
 }
@@ -0,0 +1,49 @@
+use crate::*;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Undelegate<'info> {
+    #[account(has_one = authority @ StakingErrors::Unauthorized)]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        has_one = stake_account @ StakingErrors::InvalidStakeAccount,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: Account<'info, DelegationAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"operator", delegation_account.operator.as_ref()],
+        bump,
+    )]
+    pub operator_account: Account<'info, OperatorAccount>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> Undelegate<'info> {
+    pub fn handler(&mut self) -> Result<Undelegated> {
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(
+            self.delegation_account.is_delegated(),
+            StakingErrors::NotDelegated
+        );
+        self.delegation_account.check_cooldown(now)?;
+
+        let weight: u128 = self.delegation_account.weight;
+        self.operator_account.remove(weight)?;
+        self.delegation_account.undelegate(now);
+
+        Ok(Undelegated {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            operator: self.operator_account.operator,
+            weight,
+            operator_weight: self.operator_account.delegated_weight,
+        })
+    }
+}
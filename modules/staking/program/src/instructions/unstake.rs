@@ -7,6 +7,7 @@ use effect_common::constants::SECONDS_PER_DAY;
 use effect_reward::program::EffectReward;
 use effect_vesting::program::EffectVesting;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct Unstake<'info> {
     #[account(mut)]
@@ -15,6 +16,7 @@ pub struct Unstake<'info> {
     #[account(
         mut,
         has_one = authority @ StakingErrors::Unauthorized,
+        has_one = mint @ StakingErrors::InvalidMint,
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
@@ -27,13 +29,14 @@ pub struct Unstake<'info> {
     )]
     pub stake_vault_token_account: Account<'info, TokenAccount>,
 
+    /// CHECK: synced when the stake entered the reward pool.
     #[account(
-        constraint = reward_account.data_is_empty() @ StakingErrors::InvalidRewardAccount,
+        mut,
         seeds = [stake_account.key().as_ref()],
         bump,
         seeds::program = reward_program.key(),
     )]
-    pub reward_account: SystemAccount<'info>,
+    pub reward_account: UncheckedAccount<'info>,
 
     #[account(
         signer,
@@ -66,18 +69,78 @@ pub struct Unstake<'info> {
 
     pub rent: Sysvar<'info, Rent>,
 
+    #[account(address = settings_account.mint @ StakingErrors::InvalidMint)]
     pub mint: Account<'info, Mint>,
+
+    /// Optional record that allows this unstake to be cancelled later on.
+    #[account(
+        init,
+        payer = authority,
+        space = UnstakeAccount::SIZE,
+        seeds = [b"unstake", vesting_account.key().as_ref()],
+        bump,
+    )]
+    pub unstake_account: Option<Account<'info, UnstakeAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    /// CHECK: validated by the reward program, required once the stake entered the reward pool.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(mut)]
+    pub emission_account: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
 }
 
 impl<'info> Unstake<'info> {
-    pub fn handler(&mut self, amount: u64) -> Result<()> {
+    pub fn handler(
+        &mut self,
+        amount: u64,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<UnstakeStarted> {
 
         require!(
             amount <= self.stake_account.amount,
             StakingErrors::InvalidStakeAccount
         );
 
-        // determine release rate (linear)
+        // the vesting releases the amount linearly over the lock duration, the rate is indicative
         let release_rate = amount / self.stake_account.lock_duration;
 
         // open a vesting account
@@ -87,8 +150,10 @@ impl<'info> Unstake<'info> {
         open_vesting!(
             self,
             &[&vault_seed!(self.stake_account.key())],
-            release_rate,
+            amount,
             start_time,
+            u32::try_from(self.stake_account.lock_duration)
+                .map_err(|_| StakingErrors::DurationTooLong)?,
             false,
             Some([b'u'])
         )?;
@@ -102,10 +167,38 @@ impl<'info> Unstake<'info> {
             amount
         )?;
 
+        // remember where this vesting came from, so it can be cancelled
+        if let Some(unstake_account) = &mut self.unstake_account {
+            unstake_account.init(
+                self.stake_account.key(),
+                self.stake_account.stake_start_time,
+            );
+        }
+
         // deduct the amount from the stake account
         self.stake_account.unstake(amount)?;
+        self.settings_account
+            .check_stake_amount(self.stake_account.amount)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+        sync_reward_if_entered!(
+            self,
+            Some(&self.reward_program),
+            Some(&self.reward_account),
+            pools
+        );
 
-        Ok(())
+        Ok(UnstakeStarted {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            total_amount: self.stake_account.amount,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+            vesting_account: self.vesting_account.key(),
+            vesting_start_time: start_time,
+            release_rate,
+        })
 
     }
 }
@@ -0,0 +1,175 @@
+use crate::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+use effect_common::cpi;
+
+use effect_reward::program::EffectReward;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UnstakeInstant<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = authority @ StakingErrors::Unauthorized,
+        has_one = mint @ StakingErrors::InvalidMint,
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = stake_vault_token_account,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: synced when the stake entered the reward pool.
+    #[account(
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reward_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"settings"],
+        bump,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: validated by the reward program, required once the stake entered the reward pool.
+    #[account(
+        mut,
+        seeds = [b"reflection", mint.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+    )]
+    pub reflection_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: validated by the reward program when syncing.
+    #[account(mut)]
+    pub emission_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: only used to derive the intermediate reward vault.
+    #[account(address = reward_vault_key(&mint.key()) @ StakingErrors::InvalidVault)]
+    pub reward_vault_token_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [reward_vault_token_account.key().as_ref()],
+        bump,
+        seeds::program = reward_program.key(),
+        token::mint = mint,
+    )]
+    pub intermediate_reward_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(address = settings_account.mint @ StakingErrors::InvalidMint)]
+    pub mint: Account<'info, Mint>,
+
+    pub reward_program: Program<'info, EffectReward>,
+
+    pub token_program: Program<'info, Token>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints"],
+        bump,
+    )]
+    pub total_checkpoint_account: AccountLoader<'info, TotalCheckpointAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"checkpoints", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub stake_checkpoint_account: AccountLoader<'info, StakeCheckpointAccount>,
+
+    /// CHECK: the delegation of the stake, its weight follows the stake once delegated.
+    #[account(
+        mut,
+        seeds = [b"delegation", stake_account.key().as_ref()],
+        bump,
+    )]
+    pub delegation_account: UncheckedAccount<'info>,
+
+    /// CHECK: validated against the delegation when the stake is delegated.
+    #[account(mut)]
+    pub operator_account: Option<UncheckedAccount<'info>>,
+}
+
+/// The reward vault of the reward pool of `mint`, the penalty goes to its intermediate vault.
+fn reward_vault_key(mint: &Pubkey) -> Pubkey {
+    let (reflection, _) =
+        Pubkey::find_program_address(&[b"reflection", mint.as_ref()], &effect_reward::ID);
+    Pubkey::find_program_address(&[reflection.as_ref()], &effect_reward::ID).0
+}
+
+impl<'info> UnstakeInstant<'info> {
+    pub fn handler(
+        &mut self,
+        amount: u64,
+        pools: &[AccountInfo<'info>],
+    ) -> Result<UnstakedInstant> {
+        require!(
+            amount <= self.stake_account.amount,
+            StakingErrors::InvalidStakeAccount
+        );
+
+        // the penalty is redistributed to the remaining stakers
+        let now: i64 = Clock::get()?.unix_timestamp;
+        let penalty: u64 = self
+            .settings_account
+            .get_unstake_penalty(amount, &self.stake_account, now);
+
+        transfer_tokens_from_vault!(
+            self,
+            stake_vault_token_account,
+            intermediate_reward_vault_token_account,
+            &[vault_seed!(self.stake_account.key())],
+            penalty
+        )?;
+
+        // release the rest immediately
+        transfer_tokens_from_vault!(
+            self,
+            stake_vault_token_account,
+            recipient_token_account,
+            &[vault_seed!(self.stake_account.key())],
+            amount - penalty
+        )?;
+
+        // deduct the amount from the stake account
+        self.stake_account.unstake(amount)?;
+        self.settings_account
+            .check_stake_amount(self.stake_account.amount)?;
+        record_checkpoints!(self);
+        sync_delegation_if_delegated!(self);
+
+        sync_reward_if_entered!(
+            self,
+            Some(&self.reward_program),
+            Some(&self.reward_account),
+            pools
+        );
+
+        Ok(UnstakedInstant {
+            stake_account: self.stake_account.key(),
+            authority: self.authority.key(),
+            amount,
+            penalty,
+            total_amount: self.stake_account.amount,
+            stake_start_time: self.stake_account.stake_start_time,
+            weighted_amount: self.stake_account.weighted_amount,
+        })
+    }
+}
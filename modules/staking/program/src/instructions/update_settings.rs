@@ -0,0 +1,36 @@
+use crate::*;
+use anchor_spl::token::TokenAccount;
+
+#[derive(Accounts)]
+pub struct UpdateSettings<'info> {
+    #[account(
+        mut,
+        seeds = [b"settings"],
+        bump,
+        has_one = authority @ StakingErrors::Unauthorized,
+    )]
+    pub settings_account: Account<'info, SettingsAccount>,
+
+    #[account(token::mint = settings_account.mint)]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: any account can become the new settings authority.
+    pub new_authority: UncheckedAccount<'info>,
+
+    /// CHECK: any account can become the new slashing authority.
+    pub new_slash_authority: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+}
+
+impl<'info> UpdateSettings<'info> {
+    pub fn handler(&mut self, unstake_penalty: u16, minimum_stake_amount: u64) -> Result<()> {
+        self.settings_account.set(
+            self.new_authority.key(),
+            self.new_slash_authority.key(),
+            self.token_account.key(),
+            unstake_penalty,
+            minimum_stake_amount,
+        )
+    }
+}
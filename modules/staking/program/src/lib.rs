@@ -1,5 +1,6 @@
 mod constants;
 mod errors;
+mod events;
 mod instructions;
 mod macros;
 mod state;
@@ -10,6 +11,7 @@ use anchor_lang::prelude::*;
 use effect_common::*;
 
 pub use errors::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -23,30 +25,209 @@ declare_program!(effect_reward);
 pub mod effect_staking {
     use super::*;
 
-    pub fn stake(ctx: Context<Stake>, amount: u64, duration: u128) -> Result<()> {
-        ctx.accounts.handler(amount, duration)
+    /// Initialize the [SettingsAccount](#settings-account).
+    pub fn init_settings(
+        ctx: Context<InitSettings>,
+        unstake_penalty: u16,
+        minimum_stake_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.handler(unstake_penalty, minimum_stake_amount)
+    }
+
+    /// Update the [SettingsAccount](#settings-account).
+    pub fn update_settings(
+        ctx: Context<UpdateSettings>,
+        unstake_penalty: u16,
+        minimum_stake_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.handler(unstake_penalty, minimum_stake_amount)
+    }
+
+    /// Initialize the [TotalCheckpointAccount](#total-checkpoint-account) with the amount staked
+    /// so far and its weight.
+    pub fn init_total_checkpoints(
+        ctx: Context<InitTotalCheckpoints>,
+        amount: u64,
+        weighted_amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.handler(amount, weighted_amount)
+    }
+
+    /// Initialize a [StakeCheckpointAccount](#stake-checkpoint-account) for an existing stake.
+    pub fn init_checkpoints(ctx: Context<InitCheckpoints>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Create a [StakeAccount](#stake-account) locked for the duration of lock `tier`.
+    pub fn stake(ctx: Context<Stake>, amount: u64, tier: u8) -> Result<()> {
+        let event = ctx.accounts.handler(amount, tier)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Create a [StakeAccount](#stake-account) at the next derived address of the authority.
+    pub fn stake_pda(ctx: Context<StakePda>, amount: u64, tier: u8) -> Result<()> {
+        let event = ctx.accounts.handler(amount, tier)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
-    pub fn stake_genesis(
-        ctx: Context<GenesisStake>,
+    pub fn stake_genesis<'info>(
+        ctx: Context<'_, '_, '_, 'info, GenesisStake<'info>>,
         amount: u64,
         stake_start_time: i64,
     ) -> Result<()> {
-        ctx.accounts.handler(amount, stake_start_time)
+        let event = ctx
+            .accounts
+            .handler(amount, stake_start_time, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
     /// Start the unstake duration.
-    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
-        ctx.accounts.handler(amount)
+    pub fn unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, Unstake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(amount, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Unstake immediately, paying a penalty to the reward pool instead of vesting.
+    pub fn unstake_instant<'info>(
+        ctx: Context<'_, '_, '_, 'info, UnstakeInstant<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(amount, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Cancel an unstake and return the unvested tokens to the [StakeAccount](#stake-account).
+    pub fn cancel_unstake<'info>(
+        ctx: Context<'_, '_, '_, 'info, CancelUnstake<'info>>,
+        restore_start_time: bool,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(restore_start_time, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
     /// Top-up `amount` of [NOS](/tokens/token) of a [StakeAccount](#stake-account).
-    pub fn topup(ctx: Context<Topup>, amount: u64) -> Result<()> {
-        ctx.accounts.handler(amount)
+    pub fn topup<'info>(
+        ctx: Context<'_, '_, '_, 'info, Topup<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(amount, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
     }
 
     /// Close a [StakeAccount](#stake-account) and [VaultAccount](#vault-account).
-    pub fn close(ctx: Context<Close>) -> Result<()> {
+    /// The unstake records of the stake are given as remaining accounts and closed along with it.
+    pub fn close<'info>(ctx: Context<'_, '_, '_, 'info, Close<'info>>) -> Result<()> {
+        let event = ctx.accounts.handler(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Slash `amount` of a [StakeAccount](#stake-account) to the treasury or the reward pool.
+    /// The reward pools the stake entered are given as remaining accounts and follow the slash.
+    pub fn slash<'info>(
+        ctx: Context<'_, '_, '_, 'info, Slash<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(amount, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Delegate the weight of a [StakeAccount](#stake-account) to an operator.
+    pub fn delegate(ctx: Context<Delegate>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Remove the delegation of a [StakeAccount](#stake-account) from its operator.
+    pub fn undelegate(ctx: Context<Undelegate>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Update a delegated weight to the current weight of its [StakeAccount](#stake-account).
+    pub fn sync_delegation(ctx: Context<SyncDelegation>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
+    /// Create the liquid stake pool and its receipt mint.
+    pub fn init_liquid_pool(ctx: Context<InitLiquidPool>) -> Result<()> {
+        let event = ctx.accounts.handler()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Stake `amount` into the liquid stake pool against receipts.
+    pub fn liquid_stake<'info>(
+        ctx: Context<'_, '_, '_, 'info, LiquidStake<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(amount, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Redeem `receipts` of the liquid stake pool, paying the penalty of the full pool lock.
+    pub fn liquid_redeem<'info>(
+        ctx: Context<'_, '_, '_, 'info, LiquidRedeem<'info>>,
+        receipts: u64,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(receipts, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Claim the rewards of the liquid stake pool and stake them into the pool.
+    pub fn liquid_compound<'info>(
+        ctx: Context<'_, '_, '_, 'info, LiquidCompound<'info>>,
+    ) -> Result<()> {
+        let event = ctx.accounts.handler(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Add `amount` of rewards to a [StakeAccount](#stake-account), invoked by the reward program.
+    pub fn compound(ctx: Context<Compound>, amount: u64) -> Result<()> {
+        let event = ctx.accounts.handler(amount)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Close an empty [StakeAccount](#stake-account) past its lock, refunding the rent to its authority.
+    /// The unstake records of the stake are given as remaining accounts and closed along with it.
+    /// A stake that never held tokens may await a migration claim, only its authority closes it.
+    pub fn close_empty<'info>(ctx: Context<'_, '_, '_, 'info, CloseEmpty<'info>>) -> Result<()> {
+        let event = ctx.accounts.handler(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Get the aggregated voting power of the given [StakeAccount](#stake-account)s of an authority.
+    pub fn get_voting_power<'info>(
+        ctx: Context<'_, '_, '_, 'info, GetVotingPower<'info>>,
+    ) -> Result<u128> {
+        ctx.accounts.handler(ctx.remaining_accounts)
+    }
+
+    /// Get the stake amount, or the total amount staked, at or before `timestamp`.
+    pub fn get_checkpoint(ctx: Context<GetCheckpoint>, timestamp: i64) -> Result<u64> {
+        ctx.accounts.handler(timestamp)
+    }
+
+    /// Record the mint on a legacy [StakeAccount](#stake-account).
+    pub fn migrate_mint(ctx: Context<MigrateMint>) -> Result<()> {
         ctx.accounts.handler()
     }
 }
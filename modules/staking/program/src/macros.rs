@@ -9,13 +9,198 @@ macro_rules! vault_seed {
     }};
 }
 
+#[macro_export]
+macro_rules! liquid_seed {
+    ($mint_key:expr) => {{
+        let bump = Pubkey::find_program_address(&[b"liquid", $mint_key.as_ref()], &id()).1;
+        &[b"liquid".as_ref(), $mint_key.as_ref(), &[bump]][..]
+    }};
+}
+
+/// Record the stake in its checkpoints and move the total along, a new stake starts its history.
+#[macro_export]
+macro_rules! record_checkpoints {
+    ($accounts: expr) => {{
+        let clock = Clock::get()?;
+        let amount: u64 = $accounts.stake_account.amount;
+        let weighted_amount: u64 = $accounts.stake_account.checkpoint_weighted_amount()?;
+
+        let mut stake_checkpoints =
+            StakeCheckpointAccount::load_or_init(&$accounts.stake_checkpoint_account)?;
+        $accounts.total_checkpoint_account.load_mut()?.update(
+            &stake_checkpoints.latest(),
+            amount,
+            weighted_amount,
+            &clock,
+        )?;
+        stake_checkpoints.record(amount, weighted_amount, &clock);
+    }};
+}
+
+#[macro_export]
+macro_rules! sync_reward {
+    ($accounts: expr, $pools: expr) => {
+        sync_reward!(
+            $accounts,
+            $accounts.reward_program,
+            $accounts.reward_account,
+            $accounts.reflection_account,
+            $accounts.emission_account,
+            $pools
+        )
+    };
+    (
+        $accounts: expr,
+        $reward_program: expr,
+        $reward_account: expr,
+        $reflection_account: expr,
+        $emission_account: expr,
+        $pools: expr
+    ) => {
+        effect_reward::cpi::sync(
+            CpiContext::new(
+                $reward_program.to_account_info(),
+                effect_reward::cpi::accounts::Sync {
+                    stake_account: $accounts.stake_account.to_account_info(),
+                    reward_account: $reward_account.to_account_info(),
+                    stake_vault_token_account: $accounts
+                        .stake_vault_token_account
+                        .to_account_info(),
+                    reflection_account: $reflection_account.to_account_info(),
+                    emission_account: $emission_account.to_account_info(),
+                    stake_program: $accounts.program.to_account_info(),
+                },
+            )
+            // the pools the stake entered are synced along with its reward account
+            .with_remaining_accounts($pools.to_vec()),
+        )
+    };
+}
+
+/// Sync the reward account of the stake, if the stake entered the reward pool.
+#[macro_export]
+macro_rules! sync_reward_if_entered {
+    ($accounts: expr, $reward_program: expr, $reward_account: expr, $pools: expr) => {{
+        if let Some(reward_account) = $reward_account {
+            if !reward_account.data_is_empty() {
+                let reward_program = $reward_program.ok_or(StakingErrors::InvalidRewardAccount)?;
+                let reflection_account = $accounts
+                    .reflection_account
+                    .as_ref()
+                    .ok_or(StakingErrors::InvalidRewardAccount)?;
+                let emission_account = $accounts
+                    .emission_account
+                    .as_ref()
+                    .ok_or(StakingErrors::InvalidRewardAccount)?;
+
+                // the reward program reads the stake, so it is persisted first
+                $accounts.stake_account.exit(&$crate::ID)?;
+                sync_reward!(
+                    $accounts,
+                    reward_program,
+                    reward_account,
+                    reflection_account,
+                    emission_account,
+                    $pools
+                )?;
+            }
+        }
+    }};
+}
+
+/// Claim the rewards of the liquid stake pool and stake them, returning the compounded amount.
+#[macro_export]
+macro_rules! compound_liquid_rewards {
+    ($accounts: expr) => {{
+        effect_reward::cpi::claim(CpiContext::new_with_signer(
+            $accounts.reward_program.to_account_info(),
+            effect_reward::cpi::accounts::Claim {
+                reflection_account: $accounts.reflection_account.to_account_info(),
+                emission_account: $accounts.emission_account.to_account_info(),
+                reward_vault_token_account: $accounts.reward_vault_token_account.to_account_info(),
+                stake_account: $accounts.stake_account.to_account_info(),
+                reward_account: $accounts.reward_account.to_account_info(),
+                recipient_token_account: $accounts.reserve_token_account.to_account_info(),
+                authority: $accounts.liquid_authority.to_account_info(),
+                token_program: $accounts.token_program.to_account_info(),
+            },
+            &[liquid_seed!($accounts.mint.key())],
+        ))?;
+
+        $accounts.reserve_token_account.reload()?;
+        let amount: u64 = $accounts.reserve_token_account.amount;
+
+        // stake the rewards, which grows the value of each receipt
+        if amount > 0 {
+            cpi::transfer_tokens(
+                $accounts.token_program.to_account_info(),
+                $accounts.reserve_token_account.to_account_info(),
+                $accounts.stake_vault_token_account.to_account_info(),
+                $accounts.liquid_authority.to_account_info(),
+                &[liquid_seed!($accounts.mint.key())],
+                amount,
+            )?;
+            let now: i64 = Clock::get()?.unix_timestamp;
+            $accounts.stake_account.topup(amount, now, now)?;
+            record_checkpoints!($accounts);
+        }
+
+        amount
+    }};
+}
+
+/// Close the delegation, slashes and the given unstake records of a closing stake, its checkpoints
+/// are kept as history.
+#[macro_export]
+macro_rules! close_stake_records {
+    ($accounts: expr, $unstake_accounts: expr) => {
+        release_delegation(
+            &$accounts.delegation_account.to_account_info(),
+            $accounts
+                .operator_account
+                .as_ref()
+                .map(|account| account.to_account_info())
+                .as_ref(),
+        )?;
+        for account in [
+            &$accounts.delegation_account,
+            &$accounts.slash_account,
+        ] {
+            close_stake_record(&account.to_account_info(), &$accounts.authority.to_account_info())?;
+        }
+        for unstake_account in $unstake_accounts {
+            close_unstake_record(
+                &$accounts.stake_account.key(),
+                unstake_account,
+                &$accounts.authority.to_account_info(),
+            )?;
+        }
+    };
+}
+
+/// Move the delegated weight along with the stake, if the stake is delegated.
+#[macro_export]
+macro_rules! sync_delegation_if_delegated {
+    ($accounts: expr) => {{
+        sync_delegated_weight(
+            &$accounts.delegation_account.to_account_info(),
+            $accounts
+                .operator_account
+                .as_ref()
+                .map(|account| account.to_account_info())
+                .as_ref(),
+            $accounts.stake_account.weighted_amount,
+        )?;
+    }};
+}
+
 #[macro_export]
 macro_rules! open_vesting {
-    ($accounts: expr, $seeds: expr, $release_rate: expr, $start_time: expr, $is_closable:expr, $tag: expr ) => {
-        effect_vesting::cpi::open(
+    ($accounts: expr, $seeds: expr, $amount: expr, $start_time: expr, $duration: expr, $is_closable:expr, $tag: expr ) => {
+        effect_vesting::cpi::open_schedule(
             CpiContext::new_with_signer(
                 $accounts.vesting_program.to_account_info(),
-                effect_vesting::cpi::accounts::Open {
+                effect_vesting::cpi::accounts::OpenSchedule {
                     authority: $accounts.authority.to_account_info(),
                     vesting_account: $accounts.vesting_account.to_account_info(),
                     recipient_token_account: $accounts.recipient_token_account.to_account_info(),
@@ -29,10 +214,33 @@ macro_rules! open_vesting {
                 },
                 $seeds,
             ),
-            $release_rate,
+            $amount,
             $start_time,
+            $duration,
             $is_closable,
             $tag,
         )
     };
 }
+
+#[macro_export]
+macro_rules! cancel_vesting {
+    ($accounts: expr, $seeds: expr) => {
+        effect_vesting::cpi::cancel(CpiContext::new_with_signer(
+            $accounts.vesting_program.to_account_info(),
+            effect_vesting::cpi::accounts::Cancel {
+                vesting_account: $accounts.vesting_account.to_account_info(),
+                vesting_vault_token_account: $accounts
+                    .vesting_vault_token_account
+                    .to_account_info(),
+                recipient_token_account: $accounts.recipient_token_account.to_account_info(),
+                stake_account: $accounts.stake_account.to_account_info(),
+                stake_vault_token_account: $accounts.stake_vault_token_account.to_account_info(),
+                authority: $accounts.authority.to_account_info(),
+                staking_program: $accounts.staking_program.to_account_info(),
+                token_program: $accounts.token_program.to_account_info(),
+            },
+            $seeds,
+        ))
+    };
+}
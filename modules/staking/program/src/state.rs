@@ -1,19 +1,82 @@
 use anchor_lang::prelude::*;
-use effect_common::constants::{SECONDS_PER_DAY, STAKE_AGE_MAX_DAYS};
+use effect_common::constants::{
+    BASIS_POINTS, SECONDS_PER_DAY, STAKE_AGE_MAX_DAYS, STAKE_DURATION_MAX,
+};
 
+use crate::constants::{
+    DELEGATION_COOLDOWN_DAYS, LOCK_TIER_DURATIONS, LOCK_TIER_MULTIPLIERS, SLASH_COOLDOWN_DAYS, SLASH_MAX_BASIS_POINTS, STAKE_CHECKPOINTS,
+    TOTAL_CHECKPOINTS,
+};
+use crate::StakingErrors;
+use std::cell::RefMut;
+
+/// The `SettingsAccount` holds the staking configuration, managed by its authority.
+#[account]
 pub struct SettingsAccount {
     pub authority: Pubkey,
     pub token_account: Pubkey,
+    pub unstake_penalty: u16,
+    pub minimum_stake_amount: u64,
+    /// The mint accepted for stakes, fixed when the settings are created.
+    pub mint: Pubkey,
+    pub slash_authority: Pubkey,
 }
 
 impl SettingsAccount {
     pub const SIZE: usize = 8 + std::mem::size_of::<SettingsAccount>();
 
-    pub fn set(&mut self, authority: Pubkey, token_account: Pubkey) -> Result<()> {
+    pub fn set(
+        &mut self,
+        authority: Pubkey,
+        slash_authority: Pubkey,
+        token_account: Pubkey,
+        unstake_penalty: u16,
+        minimum_stake_amount: u64,
+    ) -> Result<()> {
+        require!(
+            unstake_penalty as u64 <= BASIS_POINTS,
+            StakingErrors::InvalidPenalty
+        );
+
         self.authority = authority;
+        self.slash_authority = slash_authority;
         self.token_account = token_account;
+        self.unstake_penalty = unstake_penalty;
+        self.minimum_stake_amount = minimum_stake_amount;
+        Ok(())
+    }
+
+    /// A stake either holds at least the minimum amount or is emptied completely, new stakes are
+    /// also checked to not be empty by `check_stake`.
+    pub fn check_stake_amount(&self, amount: u64) -> Result<()> {
+        require!(
+            amount == 0 || amount >= self.minimum_stake_amount,
+            StakingErrors::AmountNotEnough
+        );
         Ok(())
     }
+
+    /// The penalty for skipping the unstake vesting, it shrinks linearly with the remaining lock.
+    pub fn get_unstake_penalty(
+        &self,
+        amount: u64,
+        stake_account: &StakeAccount,
+        now: i64,
+    ) -> u64 {
+        self.get_penalty(amount, stake_account.remaining_lock(now))
+    }
+
+    /// The penalty for redeeming liquid receipts, charged as if the full lock of the pool remained.
+    pub fn get_redeem_penalty(&self, amount: u64, stake_account: &StakeAccount) -> u64 {
+        self.get_penalty(amount, stake_account.lock_duration)
+    }
+
+    fn get_penalty(&self, amount: u64, lock: u64) -> u64 {
+        let lock: u128 = std::cmp::min(lock as u128, STAKE_DURATION_MAX);
+
+        (amount as u128 * self.unstake_penalty as u128 * lock
+            / (BASIS_POINTS as u128 * STAKE_DURATION_MAX)) as u64
+    }
 }
 
 #[account]
@@ -24,6 +87,11 @@ pub struct StakeAccount {
     pub stake_start_time: i64,
     pub weighted_amount: u128,
     pub mint: Pubkey,
+    /// Lock tier of the stake, fits in the padding so existing stakes read as tier `0`.
+    pub tier: u8,
+    /// When the lock started, unlike the stake time it is never backdated. Fits in the padding,
+    /// existing stakes read `0` and keep locking from their stake time.
+    pub lock_start_time: i64,
 }
 
 impl StakeAccount {
@@ -31,24 +99,70 @@ impl StakeAccount {
         &mut self,
         amount: u64,
         authority: Pubkey,
-        lock_duration: u64,
+        tier: u8,
         stake_start_time: i64,
-    ) {
+        mint: Pubkey,
+        now: i64,
+    ) -> Result<()> {
         self.amount = amount;
         self.authority = authority;
-        self.lock_duration = lock_duration;
+        self.lock_duration = StakeAccount::lock_tier_duration(tier)?;
+        self.tier = tier;
+        self.mint = mint;
+        self.stake_start_time =
+            std::cmp::max(stake_start_time, StakeAccount::min_stake_start_time(now));
+        self.lock_start_time = now;
 
-        if stake_start_time
-            < Clock::get().unwrap().unix_timestamp
-                - STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64
-        {
-            self.stake_start_time = Clock::get().unwrap().unix_timestamp
-                - STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64;
+        self.update_weighted_amount();
+        Ok(())
+    }
+
+    /// The lock duration of a tier from [LOCK_TIER_DURATIONS].
+    pub fn lock_tier_duration(tier: u8) -> Result<u64> {
+        LOCK_TIER_DURATIONS
+            .get(tier as usize)
+            .copied()
+            .ok_or(StakingErrors::InvalidTier.into())
+    }
+
+    /// The earliest start time a stake can have, stake age is capped at [STAKE_AGE_MAX_DAYS].
+    fn min_stake_start_time(now: i64) -> i64 {
+        now - STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64
+    }
+
+    /// The age of the stake in seconds.
+    pub fn stake_age(&self, now: i64) -> u64 {
+        let stake_start_time: i64 = std::cmp::max(
+            self.stake_start_time,
+            StakeAccount::min_stake_start_time(now),
+        );
+
+        std::cmp::max(now - stake_start_time, 0) as u64
+    }
+
+    fn lock_start(&self) -> i64 {
+        if self.lock_start_time == 0 {
+            self.stake_start_time
         } else {
-            self.stake_start_time = stake_start_time;
+            self.lock_start_time
         }
+    }
 
-        self.update_weighted_amount();
+    /// When the lock of the stake has passed.
+    pub fn lock_end(&self) -> i64 {
+        self.lock_start().saturating_add(self.lock_duration as i64)
+    }
+
+    /// The seconds left until the lock of the stake has passed.
+    pub fn remaining_lock(&self, now: i64) -> u64 {
+        std::cmp::max(self.lock_end().saturating_sub(now), 0) as u64
+    }
+
+    /// The voting power of the stake, its weighted amount with an age bonus of up to 100%.
+    pub fn voting_power(&self, now: i64) -> u128 {
+        let max_age: u128 = STAKE_AGE_MAX_DAYS as u128 * SECONDS_PER_DAY;
+
+        self.weighted_amount * (max_age + self.stake_age(now) as u128) / max_age
     }
 
     fn dilute_stake_time(
@@ -56,40 +170,693 @@ impl StakeAccount {
         current_amount: u64,
         new_time: i64,
         new_amount: u64,
-    ) -> i64 {
-        let total_amount = current_amount + new_amount;
-        let weighted_time = ((current_time as i128 * current_amount as i128)
-            + (new_time as i128 * new_amount as i128))
-            / total_amount as i128;
+    ) -> Result<i64> {
+        let total_amount: i128 = current_amount as i128 + new_amount as i128;
+
+        // nothing to weigh, the stake keeps its time
+        if total_amount == 0 {
+            return Ok(current_time);
+        }
+
+        let weighted_time: i128 = (current_time as i128)
+            .checked_mul(current_amount as i128)
+            .and_then(|current| {
+                (new_time as i128)
+                    .checked_mul(new_amount as i128)
+                    .and_then(|new| current.checked_add(new))
+            })
+            .ok_or(StakingErrors::MathOverflow)?
+            / total_amount;
 
-        weighted_time as i64
+        i64::try_from(weighted_time).map_err(|_| StakingErrors::MathOverflow.into())
     }
 
-    pub fn topup(&mut self, amount: u64, new_time: i64) {
-        let capped_stake_start: i64 = if self.stake_start_time
-            < Clock::get().unwrap().unix_timestamp
-                - STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64
-        {
-            Clock::get().unwrap().unix_timestamp
-                - STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64
-        } else {
-            self.stake_start_time
-        };
+    pub fn topup(&mut self, amount: u64, new_time: i64, now: i64) -> Result<()> {
+        let min_stake_start_time: i64 = StakeAccount::min_stake_start_time(now);
+        let capped_stake_start: i64 = std::cmp::max(self.stake_start_time, min_stake_start_time);
+        let capped_new_time: i64 = std::cmp::max(new_time, min_stake_start_time);
 
-        self.stake_start_time =
-            StakeAccount::dilute_stake_time(capped_stake_start, self.amount, new_time, amount);
+        let total_amount: u64 = self
+            .amount
+            .checked_add(amount)
+            .ok_or(StakingErrors::MathOverflow)?;
+        self.stake_start_time = StakeAccount::dilute_stake_time(
+            capped_stake_start,
+            self.amount,
+            capped_new_time,
+            amount,
+        )?;
+        // the topped up tokens lock from now, whatever stake time they bring along
+        self.lock_start_time =
+            StakeAccount::dilute_stake_time(self.lock_start(), self.amount, now, amount)?;
 
-        self.amount += amount;
+        self.amount = total_amount;
         self.update_weighted_amount();
+        Ok(())
     }
 
     pub fn unstake(&mut self, amount: u64) -> Result<()> {
-        self.amount -= amount;
+        self.amount = self
+            .amount
+            .checked_sub(amount)
+            .ok_or(StakingErrors::AmountNotEnough)?;
         self.update_weighted_amount();
         Ok(())
     }
 
+    /// The weighted amount as kept in the checkpoints.
+    pub fn checkpoint_weighted_amount(&self) -> Result<u64> {
+        u64::try_from(self.weighted_amount).map_err(|_| StakingErrors::MathOverflow.into())
+    }
+
+    /// The weighted amount is the amount boosted by the multiplier of the lock tier.
     fn update_weighted_amount(&mut self) {
-        self.weighted_amount = self.amount as u128
+        let multiplier: u128 = LOCK_TIER_MULTIPLIERS[self.tier as usize] as u128;
+        self.weighted_amount = self.amount as u128 * multiplier / BASIS_POINTS as u128;
+    }
+}
+
+/// The `DelegationAccount` records the operator a [StakeAccount] is delegated to.
+#[account]
+pub struct DelegationAccount {
+    pub stake_account: Pubkey,
+    pub operator: Pubkey,
+    pub weight: u128,
+    pub delegation_time: i64,
+}
+
+impl DelegationAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<DelegationAccount>();
+
+    pub fn is_delegated(&self) -> bool {
+        self.operator != Pubkey::default()
+    }
+
+    /// The delegation can only change once [DELEGATION_COOLDOWN_DAYS] passed since the last change.
+    pub fn check_cooldown(&self, now: i64) -> Result<()> {
+        require!(
+            now >= self.delegation_time
+                + (DELEGATION_COOLDOWN_DAYS as u128 * SECONDS_PER_DAY) as i64,
+            StakingErrors::DelegationCooldown
+        );
+        Ok(())
+    }
+
+    pub fn delegate(&mut self, stake_account: Pubkey, operator: Pubkey, weight: u128, now: i64) {
+        self.stake_account = stake_account;
+        self.operator = operator;
+        self.weight = weight;
+        self.delegation_time = now;
+    }
+
+    pub fn undelegate(&mut self, now: i64) {
+        self.operator = Pubkey::default();
+        self.weight = 0;
+        self.delegation_time = now;
+    }
+}
+
+/// The `OperatorAccount` aggregates the weight delegated to an operator.
+#[account]
+pub struct OperatorAccount {
+    pub operator: Pubkey,
+    pub delegated_weight: u128,
+    pub delegations: u64,
+}
+
+impl OperatorAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<OperatorAccount>();
+
+    pub fn add(&mut self, operator: Pubkey, weight: u128) -> Result<()> {
+        self.operator = operator;
+        self.delegated_weight = self
+            .delegated_weight
+            .checked_add(weight)
+            .ok_or(StakingErrors::MathOverflow)?;
+        self.delegations = self
+            .delegations
+            .checked_add(1)
+            .ok_or(StakingErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove(&mut self, weight: u128) -> Result<()> {
+        self.delegated_weight = self
+            .delegated_weight
+            .checked_sub(weight)
+            .ok_or(StakingErrors::MathOverflow)?;
+        self.delegations = self
+            .delegations
+            .checked_sub(1)
+            .ok_or(StakingErrors::MathOverflow)?;
+        Ok(())
+    }
+
+    pub fn sync(&mut self, old_weight: u128, new_weight: u128) -> Result<()> {
+        self.delegated_weight = self
+            .delegated_weight
+            .checked_sub(old_weight)
+            .and_then(|weight| weight.checked_add(new_weight))
+            .ok_or(StakingErrors::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// The `SlashAccount` records the slashes of a [StakeAccount].
+#[account]
+pub struct SlashAccount {
+    pub stake_account: Pubkey,
+    pub slashed_amount: u64,
+    pub slash_time: i64,
+}
+
+impl SlashAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<SlashAccount>();
+
+    /// A stake can be slashed at most [SLASH_MAX_BASIS_POINTS] once every [SLASH_COOLDOWN_DAYS].
+    pub fn slash(
+        &mut self,
+        stake_account: Pubkey,
+        amount: u64,
+        stake_amount: u64,
+        now: i64,
+    ) -> Result<()> {
+        require!(
+            amount as u128 * BASIS_POINTS as u128
+                <= stake_amount as u128 * SLASH_MAX_BASIS_POINTS as u128,
+            StakingErrors::SlashTooHigh
+        );
+        require!(
+            self.slash_time == 0
+                || now >= self.slash_time + (SLASH_COOLDOWN_DAYS as u128 * SECONDS_PER_DAY) as i64,
+            StakingErrors::SlashCooldown
+        );
+
+        self.stake_account = stake_account;
+        self.slashed_amount = self
+            .slashed_amount
+            .checked_add(amount)
+            .ok_or(StakingErrors::MathOverflow)?;
+        self.slash_time = now;
+        Ok(())
+    }
+}
+
+/// The `StakeCounterAccount` counts the derived stakes an authority has opened for a mint.
+#[account]
+pub struct StakeCounterAccount {
+    pub count: u64,
+}
+
+impl StakeCounterAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<StakeCounterAccount>();
+
+    pub fn increment(&mut self) -> Result<()> {
+        self.count = self
+            .count
+            .checked_add(1)
+            .ok_or(StakingErrors::MathOverflow)?;
+        Ok(())
+    }
+}
+
+/// A `Checkpoint` records a staked amount and its weight at a point in time.
+#[zero_copy]
+#[derive(Default)]
+pub struct Checkpoint {
+    pub timestamp: i64,
+    pub slot: u64,
+    pub amount: u64,
+    pub weighted_amount: u64,
+}
+
+/// Ring buffer logic shared by the checkpoint accounts.
+pub trait Checkpoints {
+    /// The number of checkpoints ever recorded and the ring buffer itself.
+    fn ring(&self) -> (u64, &[Checkpoint]);
+    fn ring_mut(&mut self) -> (&mut u64, &mut [Checkpoint]);
+
+    /// The most recently recorded checkpoint, empty before anything was recorded.
+    fn latest(&self) -> Checkpoint {
+        let (count, checkpoints) = self.ring();
+        if count == 0 {
+            return Checkpoint::default();
+        }
+        checkpoints[((count - 1) % checkpoints.len() as u64) as usize]
+    }
+
+    /// Record `amount` and its weight, checkpoints within the same second are merged.
+    fn record(&mut self, amount: u64, weighted_amount: u64, clock: &Clock) {
+        let (count, checkpoints) = self.ring_mut();
+        let size = checkpoints.len() as u64;
+
+        if *count > 0
+            && checkpoints[((*count - 1) % size) as usize].timestamp == clock.unix_timestamp
+        {
+            *count -= 1;
+        }
+
+        checkpoints[(*count % size) as usize] = Checkpoint {
+            timestamp: clock.unix_timestamp,
+            slot: clock.slot,
+            amount,
+            weighted_amount,
+        };
+        *count += 1;
+    }
+
+    /// Whether an amount was ever recorded, a history too long to keep always had one.
+    fn has_held_tokens(&self) -> bool {
+        let (count, checkpoints) = self.ring();
+        count > checkpoints.len() as u64
+            || checkpoints[..count as usize]
+                .iter()
+                .any(|checkpoint| checkpoint.amount > 0)
+    }
+
+    /// The checkpoint at or before `timestamp`, if it is still kept in the ring buffer.
+    fn get_checkpoint_at(&self, timestamp: i64) -> Option<Checkpoint> {
+        let (count, checkpoints) = self.ring();
+        let size = checkpoints.len() as u64;
+
+        // walk back from the newest checkpoint
+        (count.saturating_sub(size)..count)
+            .rev()
+            .map(|index| checkpoints[(index % size) as usize])
+            .find(|checkpoint| checkpoint.timestamp <= timestamp)
+    }
+}
+
+/// The `StakeCheckpointAccount` keeps the amount and weight history of a single stake.
+#[account(zero_copy)]
+pub struct StakeCheckpointAccount {
+    pub count: u64,
+    pub checkpoints: [Checkpoint; STAKE_CHECKPOINTS],
+}
+
+impl StakeCheckpointAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<StakeCheckpointAccount>();
+
+    /// Load the checkpoints of a stake for writing, initializing them when just created.
+    pub fn load_or_init<'a>(
+        loader: &'a AccountLoader<'_, StakeCheckpointAccount>,
+    ) -> Result<RefMut<'a, StakeCheckpointAccount>> {
+        loader.load_mut().or_else(|_| loader.load_init())
+    }
+}
+
+impl Checkpoints for StakeCheckpointAccount {
+    fn ring(&self) -> (u64, &[Checkpoint]) {
+        (self.count, &self.checkpoints)
+    }
+
+    fn ring_mut(&mut self) -> (&mut u64, &mut [Checkpoint]) {
+        (&mut self.count, &mut self.checkpoints)
+    }
+}
+
+/// The `TotalCheckpointAccount` keeps the history of the total amount and weight staked.
+///
+/// Every instruction that changes a stake amount updates it, so it has to be initialized with
+/// `init_total_checkpoints` first.
+#[account(zero_copy)]
+pub struct TotalCheckpointAccount {
+    pub count: u64,
+    pub checkpoints: [Checkpoint; TOTAL_CHECKPOINTS],
+}
+
+impl TotalCheckpointAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<TotalCheckpointAccount>();
+
+    /// Move the total from the `previous` checkpoint of a stake to its new amount and weight.
+    pub fn update(
+        &mut self,
+        previous: &Checkpoint,
+        amount: u64,
+        weighted_amount: u64,
+        clock: &Clock,
+    ) -> Result<()> {
+        let total: Checkpoint = self.latest();
+        let total_amount: u64 = total
+            .amount
+            .checked_sub(previous.amount)
+            .and_then(|total| total.checked_add(amount))
+            .ok_or(StakingErrors::MathOverflow)?;
+        let total_weighted_amount: u64 = total
+            .weighted_amount
+            .checked_sub(previous.weighted_amount)
+            .and_then(|total| total.checked_add(weighted_amount))
+            .ok_or(StakingErrors::MathOverflow)?;
+
+        self.record(total_amount, total_weighted_amount, clock);
+        Ok(())
+    }
+}
+
+impl Checkpoints for TotalCheckpointAccount {
+    fn ring(&self) -> (u64, &[Checkpoint]) {
+        (self.count, &self.checkpoints)
+    }
+
+    fn ring_mut(&mut self) -> (&mut u64, &mut [Checkpoint]) {
+        (&mut self.count, &mut self.checkpoints)
+    }
+}
+
+/// The `UnstakeAccount` links an unstake vesting back to the stake it was released from.
+#[account]
+pub struct UnstakeAccount {
+    pub stake_account: Pubkey,
+    pub stake_start_time: i64,
+}
+
+impl UnstakeAccount {
+    pub const SIZE: usize = 8 + std::mem::size_of::<UnstakeAccount>();
+
+    pub fn init(&mut self, stake_account: Pubkey, stake_start_time: i64) {
+        self.stake_account = stake_account;
+        self.stake_start_time = stake_start_time;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const NOW: i64 = 1_750_000_000;
+
+    fn stake(amount: u64, stake_start_time: i64, now: i64) -> StakeAccount {
+        let mut stake_account = StakeAccount {
+            amount: 0,
+            authority: Pubkey::default(),
+            lock_duration: 0,
+            stake_start_time: 0,
+            weighted_amount: 0,
+            mint: Pubkey::default(),
+            tier: 0,
+            lock_start_time: 0,
+        };
+        stake_account
+            .init(amount, Pubkey::default(), 0, stake_start_time, Pubkey::default(), now)
+            .unwrap();
+        stake_account
+    }
+
+    fn assert_invariants(stake_account: &StakeAccount, vault: u64, now: i64) {
+        assert_eq!(stake_account.amount, vault);
+        assert_eq!(stake_account.weighted_amount, vault as u128);
+        assert!(stake_account.stake_start_time >= StakeAccount::min_stake_start_time(now));
+        assert!(stake_account.stake_start_time <= now);
+    }
+
+    #[test]
+    fn lock_tier_sets_duration_and_boost() {
+        let mut stake_account = stake(0, NOW, NOW);
+        stake_account
+            .init(1_000, Pubkey::default(), 3, NOW, Pubkey::default(), NOW)
+            .unwrap();
+
+        assert_eq!(stake_account.lock_duration, LOCK_TIER_DURATIONS[3]);
+        assert_eq!(stake_account.weighted_amount, 1_500);
+        assert_eq!(
+            stake_account
+                .init(1_000, Pubkey::default(), 4, NOW, Pubkey::default(), NOW)
+                .unwrap_err(),
+            StakingErrors::InvalidTier.into()
+        );
+    }
+
+    #[test]
+    fn unstake_penalty_shrinks_with_the_remaining_lock() {
+        let settings_account = SettingsAccount {
+            authority: Pubkey::default(),
+            token_account: Pubkey::default(),
+            unstake_penalty: 5_000,
+            minimum_stake_amount: 0,
+            mint: Pubkey::default(),
+            slash_authority: Pubkey::default(),
+        };
+        let mut stake_account = stake(0, NOW, NOW);
+        stake_account
+            .init(1_000_000, Pubkey::default(), 3, NOW, Pubkey::default(), NOW)
+            .unwrap();
+        let lock_duration: i64 = stake_account.lock_duration as i64;
+        let penalty =
+            |now: i64| settings_account.get_unstake_penalty(1_000_000, &stake_account, now);
+
+        // a full year of lock left pays the full penalty
+        assert_eq!(penalty(NOW), 500_000);
+        assert_eq!(penalty(NOW + lock_duration / 2), 250_000);
+        assert_eq!(penalty(NOW + lock_duration), 0);
+        assert_eq!(penalty(NOW + 2 * lock_duration), 0);
+    }
+
+    #[test]
+    fn backdated_stakes_lock_from_now() {
+        let backdated: i64 = NOW - 900 * SECONDS_PER_DAY as i64;
+        let mut stake_account = stake(0, backdated, NOW);
+        stake_account.topup(1_000_000, backdated, NOW).unwrap();
+
+        // the stake keeps its age, the lock is not shortened by it
+        assert_eq!(stake_account.stake_start_time, backdated);
+        assert_eq!(stake_account.remaining_lock(NOW), stake_account.lock_duration);
+
+        // half of the stake topped up later locks for half as long again
+        let later: i64 = NOW + stake_account.lock_duration as i64;
+        stake_account.topup(1_000_000, backdated, later).unwrap();
+        assert_eq!(stake_account.remaining_lock(later), stake_account.lock_duration / 2);
+
+        // stakes from before the lock start was recorded lock from their stake time
+        stake_account.lock_start_time = 0;
+        assert_eq!(
+            stake_account.lock_end(),
+            stake_account.stake_start_time + stake_account.lock_duration as i64
+        );
+    }
+
+    #[test]
+    fn redeem_penalty_ignores_the_elapsed_lock() {
+        let settings_account = SettingsAccount {
+            authority: Pubkey::default(),
+            token_account: Pubkey::default(),
+            unstake_penalty: 5_000,
+            minimum_stake_amount: 0,
+            mint: Pubkey::default(),
+            slash_authority: Pubkey::default(),
+        };
+        let mut stake_account = stake(0, NOW, NOW);
+        stake_account
+            .init(1_000_000, Pubkey::default(), 0, NOW, Pubkey::default(), NOW)
+            .unwrap();
+        let penalty: u64 = settings_account.get_redeem_penalty(1_000_000, &stake_account);
+        let later: i64 = NOW + 2 * stake_account.lock_duration as i64;
+
+        // the pool lock has long passed, the redeem penalty is still charged
+        assert_eq!(settings_account.get_unstake_penalty(1_000_000, &stake_account, later), 0);
+        assert!(penalty > 0);
+        assert_eq!(
+            penalty as u128,
+            1_000_000 * 5_000 * LOCK_TIER_DURATIONS[0] as u128
+                / (BASIS_POINTS as u128 * STAKE_DURATION_MAX)
+        );
+    }
+
+    #[test]
+    fn total_checkpoints_reject_underflow_and_overflow() {
+        let mut total_checkpoint_account: TotalCheckpointAccount = bytemuck::Zeroable::zeroed();
+        let clock = Clock {
+            unix_timestamp: NOW,
+            ..Clock::default()
+        };
+
+        let stake = |amount: u64, weighted_amount: u64| Checkpoint {
+            amount,
+            weighted_amount,
+            ..Checkpoint::default()
+        };
+
+        total_checkpoint_account
+            .update(&stake(0, 0), 100, 150, &clock)
+            .unwrap();
+        assert_eq!(
+            total_checkpoint_account
+                .update(&stake(101, 0), 0, 0, &clock)
+                .unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            total_checkpoint_account
+                .update(&stake(0, 0), 0, u64::MAX, &clock)
+                .unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+
+        // the total moves by the change of the stake, its weight included
+        total_checkpoint_account
+            .update(&stake(100, 150), 40, 44, &clock)
+            .unwrap();
+        let total: Checkpoint = total_checkpoint_account.latest();
+        assert_eq!((total.amount, total.weighted_amount), (40, 44));
+    }
+
+    #[test]
+    fn only_stakes_that_held_tokens_are_dust() {
+        let mut stake_checkpoint_account: StakeCheckpointAccount = bytemuck::Zeroable::zeroed();
+        let mut clock = Clock {
+            unix_timestamp: NOW,
+            ..Clock::default()
+        };
+
+        // opened empty for a migration claim
+        stake_checkpoint_account.record(0, 0, &clock);
+        assert!(!stake_checkpoint_account.has_held_tokens());
+
+        clock.unix_timestamp += 1;
+        stake_checkpoint_account.record(100, 100, &clock);
+        clock.unix_timestamp += 1;
+        stake_checkpoint_account.record(0, 0, &clock);
+        assert!(stake_checkpoint_account.has_held_tokens());
+    }
+
+    #[test]
+    fn operator_weight_rejects_underflow() {
+        let mut operator_account = OperatorAccount {
+            operator: Pubkey::default(),
+            delegated_weight: 0,
+            delegations: 0,
+        };
+
+        operator_account.add(Pubkey::default(), 100).unwrap();
+        operator_account.sync(100, 40).unwrap();
+        assert_eq!(
+            operator_account.sync(41, 0).unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            operator_account.remove(41).unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+        operator_account.remove(40).unwrap();
+        assert_eq!(operator_account.delegated_weight, 0);
+        assert_eq!(operator_account.delegations, 0);
+    }
+
+    #[test]
+    fn slashes_and_counters_reject_overflow() {
+        let mut slash_account = SlashAccount {
+            stake_account: Pubkey::default(),
+            slashed_amount: u64::MAX - 1,
+            slash_time: 0,
+        };
+        let mut stake_counter_account = StakeCounterAccount { count: u64::MAX };
+
+        assert_eq!(
+            slash_account
+                .slash(Pubkey::default(), 2, u64::MAX, NOW)
+                .unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            stake_counter_account.increment().unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+    }
+
+    #[test]
+    fn topup_of_nothing_keeps_the_start_time() {
+        let mut stake_account = stake(0, NOW, NOW);
+
+        stake_account.topup(0, NOW + 100, NOW + 100).unwrap();
+        assert_eq!(stake_account.stake_start_time, NOW);
+        assert_eq!(stake_account.amount, 0);
+    }
+
+    #[test]
+    fn overflow_and_underflow_leave_the_stake_untouched() {
+        let mut stake_account = stake(u64::MAX - 1, NOW, NOW);
+
+        assert_eq!(
+            stake_account.topup(2, NOW, NOW).unwrap_err(),
+            StakingErrors::MathOverflow.into()
+        );
+        assert_eq!(
+            stake_account.unstake(u64::MAX).unwrap_err(),
+            StakingErrors::AmountNotEnough.into()
+        );
+        assert_invariants(&stake_account, u64::MAX - 1, NOW);
+    }
+
+    #[derive(Clone, Debug)]
+    enum StakeChange {
+        Topup { amount: u64, age: i64 },
+        Unstake { amount: u64 },
+        Wait { seconds: i64 },
+    }
+
+    fn stake_change() -> impl Strategy<Value = StakeChange> {
+        let max_age: i64 = STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64;
+
+        prop_oneof![
+            (any::<u64>(), 0..2 * max_age)
+                .prop_map(|(amount, age)| StakeChange::Topup { amount, age }),
+            (0..=u64::MAX / 4, 0..2 * max_age)
+                .prop_map(|(amount, age)| StakeChange::Topup { amount, age }),
+            any::<u64>().prop_map(|amount| StakeChange::Unstake { amount }),
+            (0..max_age / 10).prop_map(|seconds| StakeChange::Wait { seconds }),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn stake_changes_keep_invariants(
+            amount in 0..=u64::MAX / 2,
+            age in 0..2 * STAKE_AGE_MAX_DAYS as i64 * SECONDS_PER_DAY as i64,
+            changes in prop::collection::vec(stake_change(), 0..100),
+        ) {
+            let mut now: i64 = NOW;
+            let mut vault: u64 = amount;
+            let mut stake_account = stake(vault, now - age, now);
+            assert_invariants(&stake_account, vault, now);
+
+            // the start time is capped relative to the last time it changed
+            let mut changed_at: i64 = now;
+
+            for change in changes {
+                match change {
+                    StakeChange::Topup { amount, age } => {
+                        let result = stake_account.topup(amount, now - age, now);
+
+                        match vault.checked_add(amount) {
+                            Some(total) => {
+                                prop_assert!(result.is_ok());
+                                vault = total;
+                                changed_at = now;
+                            }
+                            None => prop_assert_eq!(
+                                result.unwrap_err(),
+                                StakingErrors::MathOverflow.into()
+                            ),
+                        }
+                    }
+                    StakeChange::Unstake { amount } => {
+                        let result = stake_account.unstake(amount);
+
+                        if amount <= vault {
+                            prop_assert!(result.is_ok());
+                            vault -= amount;
+                        } else {
+                            prop_assert_eq!(
+                                result.unwrap_err(),
+                                StakingErrors::AmountNotEnough.into()
+                            );
+                        }
+                    }
+                    StakeChange::Wait { seconds } => now += seconds,
+                }
+
+                assert_invariants(&stake_account, vault, changed_at);
+            }
+        }
     }
 }
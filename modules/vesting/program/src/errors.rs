@@ -21,5 +21,6 @@ pub enum VestingErrors {
     InvalidVault,
     #[msg("Claim failed")]
     ClaimFailed,
-    
+    #[msg("This pool has an invalid duration.")]
+    InvalidDuration,
 }
\ No newline at end of file
@@ -0,0 +1,83 @@
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+use effect_common::cpi;
+use effect_staking::program::EffectStaking;
+
+#[derive(Accounts)]
+pub struct Cancel<'info> {
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority @ VestingErrors::Unauthorized,
+        has_one = recipient_token_account @ VestingErrors::WrongBeneficiary,
+        constraint = vesting_account.tag == [b'u'] @ VestingErrors::WrongClaimType,
+    )]
+    pub vesting_account: Account<'info, VestingAccount>,
+
+    #[account(
+        mut,
+        seeds = [vesting_account.key().as_ref()],
+        bump
+    )]
+    pub vesting_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: only used to derive the stake vault, validated by the staking program.
+    pub stake_account: UncheckedAccount<'info>,
+
+    #[account(
+        signer,
+        mut,
+        seeds = [stake_account.key().as_ref()],
+        bump,
+        seeds::program = staking_program.key(),
+        token::mint = vesting_vault_token_account.mint,
+    )]
+    pub stake_vault_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub staking_program: Program<'info, EffectStaking>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> Cancel<'info> {
+    pub fn handler(&mut self) -> Result<()> {
+        let now: i64 = Clock::get()?.unix_timestamp;
+        let available: u64 = self.vesting_vault_token_account.amount;
+
+        // pay out whatever has vested already, only the remainder is cancelled
+        let vested: u64 = if now > self.vesting_account.start_time {
+            self.vesting_account.claim(available, now)?
+        } else {
+            0
+        };
+
+        transfer_tokens_from_vault!(
+            self,
+            vesting_vault_token_account,
+            recipient_token_account,
+            &[seeds!(self.vesting_account.key())],
+            vested
+        )?;
+
+        // return the unvested remainder to the stake vault
+        transfer_tokens_from_vault!(
+            self,
+            vesting_vault_token_account,
+            stake_vault_token_account,
+            &[seeds!(self.vesting_account.key())],
+            available - vested
+        )?;
+
+        close_vault!(
+            self,
+            vesting_vault_token_account,
+            &[seeds!(self.vesting_account.key())]
+        )
+    }
+}
@@ -1,11 +1,13 @@
 //! Instructions for Nosana Rewards.
 
+pub mod cancel;
 pub mod claim;
 pub mod close;
 pub mod open;
 pub mod update_recipient;
 
 // pub use claim_fee::*;
+pub use cancel::*;
 pub use claim::*;
 pub use close::*;
 pub use open::*;
@@ -39,6 +39,7 @@ impl<'info> Open<'info> {
         start_time: i64,
         closeable: bool,
         tag: Option<[u8; 1]>,
+        duration: u32,
     ) -> Result<()> {
         self.vesting_account.init(
             self.authority.key(),
@@ -47,6 +48,7 @@ impl<'info> Open<'info> {
             emission,
             start_time,
             tag,
-        )
+        )?;
+        self.vesting_account.update_duration(duration)
     }
 }
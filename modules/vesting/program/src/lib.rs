@@ -14,6 +14,8 @@ pub use state::*;
 
 inject_declare_id_output!("../../../target/deploy/effect_vesting-keypair.json");
 
+declare_program!(effect_staking);
+
 #[program]
 pub mod effect_vesting {
     use super::*;
@@ -26,13 +28,39 @@ pub mod effect_vesting {
         tag: Option<[u8; 1]>,
     ) -> Result<()> {
         ctx.accounts
-            .handler(release_rate, start_time, is_closable, tag)
+            .handler(release_rate, start_time, is_closable, tag, 0)
+    }
+
+    /// Open a vesting account that releases everything in its vault linearly over `duration`.
+    pub fn open_schedule(
+        ctx: Context<Open>,
+        amount: u64,
+        start_time: i64,
+        duration: u32,
+        is_closable: bool,
+        tag: Option<[u8; 1]>,
+    ) -> Result<()> {
+        require!(duration > 0, errors::VestingErrors::InvalidDuration);
+
+        // the release rate is only kept for clients that display it
+        ctx.accounts.handler(
+            amount / duration as u64,
+            start_time,
+            is_closable,
+            tag,
+            duration,
+        )
     }
 
     pub fn claim(ctx: Context<Claim>) -> Result<()> {
         ctx.accounts.handler()
     }
 
+    /// Cancel an unstake, returning the unvested tokens to the stake vault.
+    pub fn cancel(ctx: Context<Cancel>) -> Result<()> {
+        ctx.accounts.handler()
+    }
+
     pub fn close(ctx: Context<Close>) -> Result<()> {
         ctx.accounts.handler()
     }
@@ -10,6 +10,9 @@ pub struct VestingAccount {
     pub is_closeable: bool,
     pub is_restricted_claim: bool,
     pub tag: [u8; 1],
+    /// Vesting duration in seconds, `0` for accounts that release at `release_rate`.
+    /// Fits in the padding of [VestingAccount::SIZE], so existing accounts read as `0`.
+    pub duration: u32,
 }
 
 #[error_code]
@@ -37,12 +40,17 @@ impl VestingAccount {
         self.release_rate = release_rate;
         self.start_time = start_time;
         self.tag = tag.unwrap_or([b'v']);
+        self.duration = 0;
         Ok(())
     }
 
     pub fn claim(&mut self, amount_available: u64, now: i64) -> Result<u64> {
-        let pool_amount: u64 = (now - self.start_time) as u64 * self.release_rate;
-        let amount_due: u64 = pool_amount - self.distributed_tokens;
+        let amount_due: u64 = if self.duration == 0 {
+            let pool_amount: u64 = (now - self.start_time) as u64 * self.release_rate;
+            pool_amount - self.distributed_tokens
+        } else {
+            self.vested_amount(amount_available, now) - self.distributed_tokens
+        };
         let amount: u64 = std::cmp::min(amount_due, amount_available);
 
         self.distributed_tokens += amount;
@@ -50,6 +58,24 @@ impl VestingAccount {
         Ok(amount)
     }
 
+    /// The amount vested by `now` when releasing the whole amount linearly over `duration`.
+    /// The total is what was distributed plus what is left in the vault, so nothing is left over
+    /// once `duration` has passed.
+    fn vested_amount(&self, amount_available: u64, now: i64) -> u64 {
+        let elapsed: u128 = std::cmp::min(
+            std::cmp::max(now - self.start_time, 0) as u128,
+            self.duration as u128,
+        );
+        let total_amount: u128 = self.distributed_tokens as u128 + amount_available as u128;
+
+        (total_amount * elapsed / self.duration as u128) as u64
+    }
+
+    pub fn update_duration(&mut self, duration: u32) -> Result<()> {
+        self.duration = duration;
+        Ok(())
+    }
+
     pub fn update_authority(&mut self, authority: Pubkey) -> Result<()> {
         self.authority = authority;
         Ok(())
@@ -60,3 +86,96 @@ impl VestingAccount {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START: i64 = 1_000;
+
+    fn schedule(duration: u32) -> VestingAccount {
+        let mut vesting_account = VestingAccount {
+            authority: Pubkey::default(),
+            recipient_token_account: Pubkey::default(),
+            distributed_tokens: 0,
+            release_rate: 0,
+            start_time: 0,
+            is_closeable: false,
+            is_restricted_claim: false,
+            tag: [b'u'],
+            duration: 0,
+        };
+        vesting_account
+            .init(Pubkey::default(), Pubkey::default(), false, 0, START, Some([b'u']))
+            .unwrap();
+        vesting_account.update_duration(duration).unwrap();
+        vesting_account
+    }
+
+    /// Claim at each of `times` and return the total that was paid out.
+    fn claim_at(vesting_account: &mut VestingAccount, amount: u64, times: &[i64]) -> u64 {
+        let mut vault: u64 = amount;
+        for now in times {
+            vault -= vesting_account.claim(vault, *now).unwrap();
+        }
+        amount - vault
+    }
+
+    #[test]
+    fn duration_fits_in_existing_accounts() {
+        // the serialized account still fits in the space of accounts opened before `duration`
+        assert_eq!(VestingAccount::SIZE, 104);
+        assert!(8 + schedule(1).try_to_vec().unwrap().len() <= VestingAccount::SIZE);
+    }
+
+    #[test]
+    fn small_amount_releases_over_the_whole_duration() {
+        let duration: u32 = 30 * 24 * 60 * 60;
+        let mut vesting_account = schedule(duration);
+
+        // 10 tokens over 30 days has no release rate, but still vests linearly
+        assert_eq!(claim_at(&mut vesting_account, 10, &[START + 1]), 0);
+        assert_eq!(claim_at(&mut vesting_account, 10, &[START + duration as i64 / 2]), 5);
+        assert_eq!(vesting_account.distributed_tokens, 5);
+    }
+
+    #[test]
+    fn odd_amount_releases_exactly_at_the_end() {
+        let duration: u32 = 7 * 24 * 60 * 60;
+        let amount: u64 = 1_000_003;
+        let mut vesting_account = schedule(duration);
+
+        // nothing is left behind one second before the end
+        let paid: u64 = claim_at(&mut vesting_account, amount, &[START + duration as i64 - 1]);
+        assert!(paid < amount);
+
+        let mut vault: u64 = amount - paid;
+        vault -= vesting_account
+            .claim(vault, START + duration as i64)
+            .unwrap();
+        assert_eq!(vault, 0);
+        assert_eq!(vesting_account.distributed_tokens, amount);
+    }
+
+    #[test]
+    fn repeated_claims_add_up_to_the_amount() {
+        let duration: u32 = 365 * 24 * 60 * 60;
+        let amount: u64 = 123_456_789;
+        let mut vesting_account = schedule(duration);
+
+        let times: Vec<i64> = (1..=97)
+            .map(|i| START + i * 325_117)
+            .chain([START + duration as i64 + 1])
+            .collect();
+        assert_eq!(claim_at(&mut vesting_account, amount, &times), amount);
+    }
+
+    #[test]
+    fn release_rate_accounts_are_unchanged() {
+        let mut vesting_account = schedule(0);
+        vesting_account.release_rate = 3;
+
+        assert_eq!(claim_at(&mut vesting_account, 100, &[START + 10]), 30);
+        assert_eq!(claim_at(&mut vesting_account, 100, &[START + 20]), 30);
+    }
+}
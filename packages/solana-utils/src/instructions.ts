@@ -1,8 +1,8 @@
 import {
   deriveRewardAccountsPda,
   deriveStakingRewardAccountPda,
-  EFFECT_REWARD_PROGRAM_ADDRESS,
   fetchMaybeRewardAccount,
+  fetchRewardConfig,
   getClaimInstructionAsync,
   getCloseInstructionAsync,
   getEnterInstructionAsync,
@@ -10,6 +10,7 @@ import {
 } from "@effectai/reward";
 
 import {
+  deriveStakeAccountsPda,
   getTopupInstructionAsync,
   getUnstakeInstructionAsync,
 } from "@effectai/staking";
@@ -18,8 +19,6 @@ import { getTopupInstructionAsync as getRewardTopupInstructionAsync } from "@eff
 
 import {
   generateKeyPairSigner,
-  getAddressEncoder,
-  getProgramDerivedAddress,
   type Address,
   type Instruction,
   type Rpc,
@@ -33,6 +32,32 @@ import {
 } from "@effectai/vesting";
 import { getCreateAssociatedTokenInstructionAsync } from "@solana-program/token";
 
+export const deriveStakeAddresses = async ({
+  mint,
+  authority,
+  index,
+}: {
+  mint: Address;
+  authority: Address;
+  index: number | bigint;
+}) => {
+  const { stakeAccount, stakeVaultAccount } = await deriveStakeAccountsPda({
+    mint,
+    authority,
+    index,
+  });
+
+  const { stakingRewardAccount } = await deriveStakingRewardAccountPda({
+    stakingAccount: stakeAccount,
+  });
+
+  return {
+    stakeAccount,
+    stakeVaultAccount,
+    stakingRewardAccount,
+  };
+};
+
 export const buildClaimRewardsInstruction = async ({
   mint,
   stakeAccount,
@@ -48,14 +73,10 @@ export const buildClaimRewardsInstruction = async ({
   mint: Address;
   signer: TransactionSigner;
 }): Promise<Instruction[]> => {
-  const [reflectionAccount] = await getProgramDerivedAddress({
-    seeds: [
-      Buffer.from("reflection", "utf-8"),
-      getAddressEncoder().encode(mint),
-    ],
-
-    programAddress: EFFECT_REWARD_PROGRAM_ADDRESS,
+  const { reflectionAccount, configAccount } = await deriveRewardAccountsPda({
+    mint,
   });
+  const rewardConfig = await fetchRewardConfig(rpc, configAccount);
 
   const vestingAccountData = await fetchVestingAccount(rpc, vestingAccount);
 
@@ -67,6 +88,7 @@ export const buildClaimRewardsInstruction = async ({
 
   const topupRewardPoolIx = await getRewardTopupInstructionAsync({
     mint,
+    treasuryTokenAccount: rewardConfig.data.treasury,
   });
 
   const claimIx = await getClaimInstructionAsync({
@@ -105,17 +127,12 @@ export const buildUnstakeInstruction = async ({
 
   const { reflectionAccount } = await deriveRewardAccountsPda({ mint });
 
-  const claimIx = await getClaimInstructionAsync({
-    stakeAccount,
-    authority: signer,
-    reflectionAccount,
-    recipientTokenAccount: userTokenAccount,
-  });
-
+  // closing the reward account pays out its pending rewards
   const closeIx = await getCloseInstructionAsync({
     stakeAccount,
     authority: signer,
     reflectionAccount,
+    recipientTokenAccount: userTokenAccount,
   });
 
   const enterRewardPoolIx = await getEnterInstructionAsync({
@@ -136,7 +153,7 @@ export const buildUnstakeInstruction = async ({
   });
 
   return maybeStakingRewardAccount.exists
-    ? [claimIx, closeIx, unstakeIx, enterRewardPoolIx]
+    ? [closeIx, unstakeIx, enterRewardPoolIx]
     : [unstakeIx, enterRewardPoolIx];
 };
 